@@ -1,13 +1,27 @@
+mod tui;
+
+use std::env;
 use oxag_ai_j::WrapperTrashinatorRobot;
+use oxag_ai_j::strategies::Strategies;
 use env_logger::Env;
 
 fn main() {
-    env_logger::Builder::from_env(Env::default().default_filter_or("debug"))
-        .format_timestamp(None)
-        .format_target(false)
-        .init();
+    let tui_mode = env::args().any(|arg| arg == "--tui");
+
+    // Logs would mess up the terminal UI, so they are only enabled in the plain mode
+    if !tui_mode {
+        env_logger::Builder::from_env(Env::default().default_filter_or("debug"))
+            .format_timestamp(None)
+            .format_target(false)
+            .init();
+    }
+
+    let mut ai_robot = WrapperTrashinatorRobot::new(20, Strategies::default());
 
-    let mut ai_robot = WrapperTrashinatorRobot::new(20);
+    if tui_mode {
+        tui::run(ai_robot).expect("Failed running the terminal UI");
+        return;
+    }
 
     let mut done = false;
 
@@ -0,0 +1,272 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{stdout, Result};
+use std::time::{Duration, Instant};
+use crossterm::event::{self, Event as TerminalEvent, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use oxag_ai_j::state::AiState;
+use oxag_ai_j::WrapperTrashinatorRobot;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::{Frame, Terminal};
+use robotics_lib::event::events::Event;
+use robotics_lib::world::tile::Content::{Bin, Fire, Garbage};
+use robotics_lib::world::tile::{Tile, TileType};
+
+/// Number of events kept in the event panel
+const MAX_EVENTS: usize = 200;
+const MIN_TICK_DELAY: Duration = Duration::from_millis(10);
+const MAX_TICK_DELAY: Duration = Duration::from_millis(5000);
+
+/// Data of the run shown by the terminal UI
+struct App {
+    known_tiles: HashMap<(usize, usize), Tile>,
+    events: VecDeque<String>,
+    tick: usize,
+    terminated: bool,
+    paused: bool,
+    step_requested: bool,
+    quit: bool,
+    tick_delay: Duration,
+}
+
+impl App {
+    fn new() -> App {
+        App {
+            known_tiles: HashMap::new(),
+            events: VecDeque::new(),
+            tick: 0,
+            terminated: false,
+            paused: false,
+            step_requested: false,
+            quit: false,
+            tick_delay: Duration::from_millis(200),
+        }
+    }
+
+    fn update(&mut self, terminated: bool, events: Vec<Event>, tiles: Vec<(Tile, (usize, usize))>) {
+        for (tile, coordinates) in tiles {
+            self.known_tiles.insert(coordinates, tile);
+        }
+
+        for event in events {
+            self.events.push_front(format!("[{}] {:?}", self.tick, event));
+        }
+        self.events.truncate(MAX_EVENTS);
+
+        self.terminated = terminated;
+        self.tick += 1;
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char(' ') | KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('n') | KeyCode::Right => {
+                self.paused = true;
+                self.step_requested = true;
+            }
+            KeyCode::Char('+') | KeyCode::Up => self.tick_delay = (self.tick_delay / 2).max(MIN_TICK_DELAY),
+            KeyCode::Char('-') | KeyCode::Down => self.tick_delay = (self.tick_delay * 2).min(MAX_TICK_DELAY),
+            _ => {}
+        }
+    }
+
+    /// Whether a process tick should be performed now
+    fn should_tick(&mut self, last_tick: Instant) -> bool {
+        if self.terminated {
+            return false;
+        }
+
+        if self.paused {
+            let step = self.step_requested;
+            self.step_requested = false;
+            return step;
+        }
+
+        last_tick.elapsed() >= self.tick_delay
+    }
+
+    fn draw(&self, frame: &mut Frame, state: &AiState) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(20), Constraint::Length(48)])
+            .split(frame.size());
+
+        let panels = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(9),
+                Constraint::Percentage(40),
+                Constraint::Min(5),
+            ])
+            .split(columns[1]);
+
+        self.draw_map(frame, columns[0], state);
+        self.draw_robot(frame, panels[0], state);
+        self.draw_tasks(frame, panels[1], state);
+        self.draw_events(frame, panels[2]);
+    }
+
+    fn draw_map(&self, frame: &mut Frame, area: Rect, state: &AiState) {
+        let (robot_row, robot_col) = state.robot_coordinates;
+        let height = area.height.saturating_sub(2) as usize;
+        let width = area.width.saturating_sub(2) as usize;
+
+        let first_row = robot_row.saturating_sub(height / 2);
+        let first_col = robot_col.saturating_sub(width / 2);
+
+        let lines: Vec<Line> = (first_row..first_row + height)
+            .map(|row| {
+                let spans: Vec<Span> = (first_col..first_col + width)
+                    .map(|col| self.tile_span((row, col), (robot_row, robot_col)))
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+
+        let block = Block::default().borders(Borders::ALL).title("Known map");
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn tile_span(&self, coordinates: (usize, usize), robot: (usize, usize)) -> Span<'static> {
+        let tile = match self.known_tiles.get(&coordinates) {
+            None => return Span::raw(" "),
+            Some(tile) => tile,
+        };
+
+        let background = match tile.tile_type {
+            TileType::DeepWater => Color::Blue,
+            TileType::ShallowWater => Color::LightBlue,
+            TileType::Sand => Color::LightYellow,
+            TileType::Grass => Color::Green,
+            TileType::Street => Color::DarkGray,
+            TileType::Hill => Color::LightGreen,
+            TileType::Mountain => Color::Gray,
+            TileType::Snow => Color::White,
+            TileType::Lava => Color::LightRed,
+            TileType::Teleport(_) => Color::Magenta,
+            TileType::Wall => Color::Black,
+        };
+        let style = Style::default().bg(background);
+
+        if coordinates == robot {
+            return Span::styled("@", style.fg(Color::White).add_modifier(Modifier::BOLD));
+        }
+
+        match tile.content {
+            Fire => Span::styled("F", style.fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Garbage(_) => Span::styled("G", style.fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Bin(_) => Span::styled("B", style.fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            _ => match tile.tile_type {
+                TileType::Teleport(_) => Span::styled("T", style.fg(Color::White)),
+                _ => Span::styled(" ", style),
+            },
+        }
+    }
+
+    fn draw_robot(&self, frame: &mut Frame, area: Rect, state: &AiState) {
+        let status = if self.terminated {
+            "terminated"
+        } else if self.paused {
+            "paused"
+        } else {
+            "running"
+        };
+
+        let backpack: Vec<String> = state
+            .backpack
+            .iter()
+            .filter(|(_, amount)| **amount > 0)
+            .map(|(content, amount)| format!("{:?}: {}", content, amount))
+            .collect();
+
+        let lines = vec![
+            Line::from(format!("Tick: {} ({}, {}ms)", self.tick, status, self.tick_delay.as_millis())),
+            Line::from(format!("Position: {:?}", state.robot_coordinates)),
+            Line::from(format!("Energy: {}{}", state.energy, if state.resting { " (resting)" } else { "" })),
+            Line::from(format!("Backpack: {}", backpack.join(", "))),
+            Line::from(""),
+            Line::from("space: pause, n: step, +/-: speed, q: quit"),
+        ];
+
+        let block = Block::default().borders(Borders::ALL).title("Robot");
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_tasks(&self, frame: &mut Frame, area: Rect, state: &AiState) {
+        let mut items = vec![ListItem::new(match &state.current_task {
+            None => "Current: none".to_string(),
+            Some(task) => format!("Current: {:?} at {:?}", task.action, task.coordinates),
+        })];
+
+        items.extend(state.pending_tasks.iter().map(|task| {
+            ListItem::new(format!("{} - {:?} at {:?}", task.priority, task.action, task.coordinates))
+        }));
+
+        let block = Block::default().borders(Borders::ALL).title("Tasks");
+        frame.render_widget(List::new(items).block(block), area);
+    }
+
+    fn draw_events(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .events
+            .iter()
+            .map(|event| ListItem::new(event.clone()))
+            .collect();
+
+        let block = Block::default().borders(Borders::ALL).title("Events");
+        frame.render_widget(List::new(items).block(block), area);
+    }
+}
+
+/// Runs the robot showing the terminal UI until the user quits
+pub fn run(mut ai_robot: WrapperTrashinatorRobot) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let result = run_loop(&mut terminal, &mut ai_robot);
+
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen)?;
+
+    result
+}
+
+fn run_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    ai_robot: &mut WrapperTrashinatorRobot,
+) -> Result<()> {
+    let mut app = App::new();
+    let mut last_tick = Instant::now();
+
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame, &ai_robot.state()))?;
+
+        let timeout = if app.paused || app.terminated {
+            Duration::from_millis(100)
+        } else {
+            app.tick_delay.saturating_sub(last_tick.elapsed())
+        };
+
+        if event::poll(timeout)? {
+            if let TerminalEvent::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+
+        if app.should_tick(last_tick) {
+            let (terminated, events, tiles) = ai_robot.ai_process_tick();
+            app.update(terminated, events, tiles);
+            last_tick = Instant::now();
+        }
+    }
+
+    Ok(())
+}
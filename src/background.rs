@@ -0,0 +1,180 @@
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, SendError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use log::{debug, info};
+use crate::recording::TickRecord;
+use crate::WrapperTrashinatorRobot;
+
+/// Time between two process ticks when the robot is running, until a `Command::SetTickRate` is sent
+pub const DEFAULT_TICK_RATE: Duration = Duration::from_millis(200);
+
+/// Commands sent to the worker thread of a `BackgroundTrashinatorRobot`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Performs a process tick every tick rate
+    Start,
+    /// Stops performing process ticks until the next `Start` or `Step`
+    Pause,
+    /// Performs a single process tick, also while paused
+    Step,
+    /// Terminates the worker thread
+    Stop,
+    /// Sets the time between two process ticks while running
+    SetTickRate(Duration),
+}
+
+/// Messages sent by the worker thread of a `BackgroundTrashinatorRobot`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Update {
+    /// A process tick was performed, with the same data written in recordings
+    Tick(TickRecord),
+    /// The worker thread terminated, either because it was stopped or because the robot terminated
+    Stopped,
+}
+
+/// Runs a `WrapperTrashinatorRobot` on a worker thread, so that visualizers don't have to block on process ticks
+///
+/// The robot is controlled by sending `Command`s and reports each process tick with an `Update`.
+/// It starts paused, the worker thread is stopped when this is dropped
+pub struct BackgroundTrashinatorRobot {
+    commands: Sender<Command>,
+    updates: Receiver<Update>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl BackgroundTrashinatorRobot {
+    /// Spawns the worker thread and creates the robot in it by calling `create_robot`
+    ///
+    /// The robot is created in the worker thread since it can't be moved between threads, e.g.
+    /// `BackgroundTrashinatorRobot::spawn(|| WrapperTrashinatorRobot::new(20, Strategies::default()))`
+    pub fn spawn<F>(create_robot: F) -> BackgroundTrashinatorRobot
+    where
+        F: FnOnce() -> WrapperTrashinatorRobot + Send + 'static,
+    {
+        let (commands, command_receiver) = channel();
+        let (update_sender, updates) = channel();
+
+        let worker = thread::spawn(move || {
+            let robot = create_robot();
+            Self::run(robot, command_receiver, &update_sender);
+
+            let _ = update_sender.send(Update::Stopped);
+        });
+
+        BackgroundTrashinatorRobot {
+            commands,
+            updates,
+            worker: Some(worker),
+        }
+    }
+
+    /// Sends a command to the worker thread, fails if the worker thread has terminated
+    pub fn send(&self, command: Command) -> Result<(), SendError<Command>> {
+        self.commands.send(command)
+    }
+
+    /// Starts performing a process tick every tick rate
+    pub fn start(&self) -> Result<(), SendError<Command>> {
+        self.send(Command::Start)
+    }
+
+    /// Stops performing process ticks until the next `start` or `step`
+    pub fn pause(&self) -> Result<(), SendError<Command>> {
+        self.send(Command::Pause)
+    }
+
+    /// Performs a single process tick
+    pub fn step(&self) -> Result<(), SendError<Command>> {
+        self.send(Command::Step)
+    }
+
+    /// Terminates the worker thread, an `Update::Stopped` is sent once it's done
+    pub fn stop(&self) -> Result<(), SendError<Command>> {
+        self.send(Command::Stop)
+    }
+
+    /// Sets the time between two process ticks while running
+    pub fn set_tick_rate(&self, tick_rate: Duration) -> Result<(), SendError<Command>> {
+        self.send(Command::SetTickRate(tick_rate))
+    }
+
+    /// Returns the next update without blocking, `None` if there is none yet or the worker thread has terminated
+    pub fn try_recv_update(&self) -> Option<Update> {
+        self.updates.try_recv().ok()
+    }
+
+    /// Returns the receiver of the updates, for callers that want to block on them or iterate over them
+    pub fn updates(&self) -> &Receiver<Update> {
+        &self.updates
+    }
+
+    /// Loop of the worker thread, returns when stopped, when the robot terminates or when the caller is gone
+    fn run(mut robot: WrapperTrashinatorRobot, commands: Receiver<Command>, updates: &Sender<Update>) {
+        let mut running = false;
+        let mut tick_rate = DEFAULT_TICK_RATE;
+        let mut last_tick: Option<Instant> = None;
+
+        loop {
+            let command = if running {
+                let next_tick = last_tick.map_or_else(Instant::now, |last_tick| last_tick + tick_rate);
+
+                match commands.recv_timeout(next_tick.saturating_duration_since(Instant::now())) {
+                    Ok(command) => Some(command),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            } else {
+                match commands.recv() {
+                    Ok(command) => Some(command),
+                    Err(_) => return,
+                }
+            };
+
+            match command {
+                Some(Command::Start) => {
+                    debug!("Background robot started");
+                    running = true;
+                    last_tick = None;
+                    continue;
+                }
+                Some(Command::Pause) => {
+                    debug!("Background robot paused");
+                    running = false;
+                    continue;
+                }
+                Some(Command::Stop) => {
+                    info!("Background robot stopped");
+                    return;
+                }
+                Some(Command::SetTickRate(rate)) => {
+                    tick_rate = rate;
+                    continue;
+                }
+                Some(Command::Step) | None => {}
+            }
+
+            let (terminated, _, _) = robot.ai_process_tick();
+            last_tick = Some(Instant::now());
+
+            let record = TickRecord::from_state(&robot.state());
+            if updates.send(Update::Tick(record)).is_err() {
+                return;
+            }
+
+            if terminated {
+                info!("Background robot terminated after {} ticks", robot.ticks());
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for BackgroundTrashinatorRobot {
+    fn drop(&mut self) {
+        let _ = self.commands.send(Command::Stop);
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
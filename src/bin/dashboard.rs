@@ -0,0 +1,42 @@
+use std::env;
+use std::thread::sleep;
+use std::time::Duration;
+use env_logger::Env;
+use oxag_ai_j::dashboard::render::DashboardView;
+use oxag_ai_j::dashboard::server::DashboardServer;
+use oxag_ai_j::WrapperTrashinatorRobot;
+
+/// Runs the robot and serves a local real-time dashboard
+///
+/// Usage: `dashboard [address] [tick_millis] [world_file]`, defaults to `127.0.0.1:8080`, 200ms and a generated world
+fn main() {
+    env_logger::Builder::from_env(Env::default().default_filter_or("info"))
+        .format_timestamp(None)
+        .format_target(false)
+        .init();
+
+    let args: Vec<String> = env::args().collect();
+    let address = args.get(1).map(String::as_str).unwrap_or("127.0.0.1:8080");
+    let tick_millis = args.get(2).and_then(|millis| millis.parse().ok()).unwrap_or(200);
+
+    let mut builder = WrapperTrashinatorRobot::builder(20);
+    if let Some(world_file) = args.get(3) {
+        builder = builder.world_file(world_file);
+    }
+    let mut ai_robot = builder.build().expect("Failed creating the robot");
+
+    let server = DashboardServer::start(address).expect("Failed starting the dashboard server");
+    let mut view = DashboardView::new();
+
+    let mut done = false;
+
+    while !done {
+        let (terminated, events, tiles) = ai_robot.ai_process_tick();
+        done = terminated;
+
+        view.update(&events, &tiles);
+        server.broadcast(&view.render(&ai_robot.state()));
+
+        sleep(Duration::from_millis(tick_millis));
+    }
+}
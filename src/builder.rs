@@ -0,0 +1,184 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use log::info;
+use robotics_lib::runner::{Robot, Runner};
+use robotics_lib::utils::LibError;
+use robotics_lib::world::world_generator::Generator;
+use worldgen_unwrap::public::WorldgeneratorUnwrap;
+use crate::energy::EnergyPolicy;
+use crate::generator::RandomWorldGenerator;
+use crate::goal::Goal;
+use crate::robot::{TrashinatorRobot, DEFAULT_DISPOSAL_THRESHOLD, DEFAULT_MAX_TASK_ATTEMPTS};
+use crate::state::AiState;
+use crate::strategies::scoring::{DefaultTaskScorer, TaskScorer};
+use crate::strategies::Strategies;
+use crate::WrapperTrashinatorRobot;
+
+/// Builder for a `WrapperTrashinatorRobot`
+///
+/// By default the world is generated by `worldgen_unwrap` without showing its GUI, this can be changed with:
+/// - `world_file`: loads a world previously saved with `worldgen_unwrap`, like `bin/world.bin`
+/// - `worldgen_gui`: shows the `worldgen_unwrap` GUI to choose the generation parameters (size, etc.)
+/// - `world_size`: generates a world of the given size with a `RandomWorldGenerator`, without any GUI
+/// - `build_with_generator`: uses any `robotics_lib` `Generator` instead of `worldgen_unwrap`
+///
+/// Every random decision of the robot comes from a single RNG, set its `seed` to reproduce a run on the same world
+pub struct WrapperTrashinatorRobotBuilder {
+    goal: Goal,
+    strategies: Strategies,
+    world_file: Option<PathBuf>,
+    worldgen_gui: bool,
+    world_size: Option<usize>,
+    seed: Option<u64>,
+    task_scorer: Box<dyn TaskScorer>,
+    disposal_threshold: usize,
+    max_task_attempts: usize,
+    energy_policy: EnergyPolicy,
+}
+
+impl WrapperTrashinatorRobotBuilder {
+    /// Creates a builder for a robot that will stop after completing `tasks_to_complete` tasks
+    pub fn new(tasks_to_complete: usize) -> WrapperTrashinatorRobotBuilder {
+        WrapperTrashinatorRobotBuilder {
+            goal: Goal::TasksCompleted(tasks_to_complete),
+            strategies: Strategies::default(),
+            world_file: None,
+            worldgen_gui: false,
+            world_size: None,
+            seed: None,
+            task_scorer: Box::new(DefaultTaskScorer::default()),
+            disposal_threshold: DEFAULT_DISPOSAL_THRESHOLD,
+            max_task_attempts: DEFAULT_MAX_TASK_ATTEMPTS,
+            energy_policy: EnergyPolicy::default(),
+        }
+    }
+
+    /// Sets when the robot terminates, replacing the number of tasks to complete
+    pub fn goal(mut self, goal: Goal) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// Sets the strategies used in each stage of the process tick
+    pub fn strategies(mut self, strategies: Strategies) -> Self {
+        self.strategies = strategies;
+        self
+    }
+
+    /// Sets the function used to compute the priority of the tasks
+    pub fn task_scorer(mut self, task_scorer: Box<dyn TaskScorer>) -> Self {
+        self.task_scorer = task_scorer;
+        self
+    }
+
+    /// Sets the amount of garbage in the backpack above which the robot goes to empty it in the closest bin
+    pub fn disposal_threshold(mut self, disposal_threshold: usize) -> Self {
+        self.disposal_threshold = disposal_threshold;
+        self
+    }
+
+    /// Sets how many times a task can fail for a transient reason before being dropped
+    pub fn max_task_attempts(mut self, max_task_attempts: usize) -> Self {
+        self.max_task_attempts = max_task_attempts;
+        self
+    }
+
+    /// Sets the energy reserve and the estimated costs of the actions, used to decide when the robot rests to regenerate energy
+    pub fn energy_policy(mut self, energy_policy: EnergyPolicy) -> Self {
+        self.energy_policy = energy_policy;
+        self
+    }
+
+    /// Loads the world from a file saved by `worldgen_unwrap` instead of generating a new one
+    pub fn world_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.world_file = Some(path.into());
+        self
+    }
+
+    /// Whether to show the `worldgen_unwrap` GUI to choose the generation parameters of the world
+    pub fn worldgen_gui(mut self, show: bool) -> Self {
+        self.worldgen_gui = show;
+        self
+    }
+
+    /// Generates a `size` x `size` world with a `RandomWorldGenerator` seeded with the `seed` of the robot
+    ///
+    /// Takes the place of `worldgen_unwrap`, so `world_file` and `worldgen_gui` are ignored
+    pub fn world_size(mut self, size: usize) -> Self {
+        self.world_size = Some(size);
+        self
+    }
+
+    /// Sets the seed of the RNG used by the robot, a random one is used if not set
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Builds the robot using `worldgen_unwrap` as the world generator, or a `RandomWorldGenerator` if `world_size` is set
+    pub fn build(mut self) -> Result<WrapperTrashinatorRobot, LibError> {
+        if let Some(size) = self.world_size {
+            // The same seed reproduces both the world and the run
+            let seed = *self.seed.get_or_insert_with(rand::random);
+
+            return self.build_with_generator(RandomWorldGenerator::new(size, seed));
+        }
+
+        let world_generator = WorldgeneratorUnwrap::init(self.worldgen_gui, self.world_file.clone());
+
+        self.build_with_generator(world_generator)
+    }
+
+    /// Builds the robot using the given world generator
+    ///
+    /// `world_file`, `worldgen_gui` and `world_size` are ignored, since they only choose the default generators
+    pub fn build_with_generator<G: Generator + 'static>(
+        self,
+        mut world_generator: G,
+    ) -> Result<WrapperTrashinatorRobot, LibError> {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        info!("Using seed {}", seed);
+
+        let state = Rc::new(RefCell::new(AiState::new()));
+        let mut runner = TrashinatorRobot::new(
+            Robot::new(),
+            state.clone(),
+            self.goal,
+            self.strategies,
+            seed,
+        );
+        runner.task_scorer = self.task_scorer;
+        runner.disposal_threshold = self.disposal_threshold;
+        runner.max_task_attempts = self.max_task_attempts;
+        runner.energy_policy = self.energy_policy;
+        let known_map = runner.known_map.clone();
+        let runner = Runner::new(Box::new(runner), &mut world_generator)?;
+
+        Ok(WrapperTrashinatorRobot {
+            runner,
+            state,
+            seed,
+            recorder: None,
+            known_map,
+            _world_generator: Box::new(world_generator),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_size_generates_a_world_of_that_size() {
+        let mut robot = WrapperTrashinatorRobotBuilder::new(1).world_size(12).seed(5).build().unwrap();
+        assert_eq!(robot.seed(), 5);
+
+        robot.ai_process_tick();
+        let snapshot = robot.known_map_snapshot();
+
+        assert!(!snapshot.tiles.is_empty());
+        assert!(snapshot.tiles.iter().all(|(_, (row, col))| *row < 12 && *col < 12));
+    }
+}
@@ -0,0 +1,30 @@
+// Receives the HTML fragments of each tick through the websocket and swaps them in the page.
+// Mirrors the out of band swaps of the HTMX websocket extension: every element of a message that has an
+// `hx-swap-oob` attribute replaces the element of the page with the same id.
+(function () {
+    const RECONNECT_MILLIS = 1000;
+
+    function swap(html) {
+        const template = document.createElement("template");
+        template.innerHTML = html;
+
+        for (const fragment of template.content.querySelectorAll("[hx-swap-oob]")) {
+            const target = document.getElementById(fragment.id);
+
+            if (target) {
+                fragment.removeAttribute("hx-swap-oob");
+                target.replaceWith(fragment);
+            }
+        }
+    }
+
+    function connect() {
+        const protocol = window.location.protocol === "https:" ? "wss:" : "ws:";
+        const socket = new WebSocket(protocol + "//" + window.location.host + "/ws");
+
+        socket.onmessage = (event) => swap(event.data);
+        socket.onclose = () => setTimeout(connect, RECONNECT_MILLIS);
+    }
+
+    connect();
+})();
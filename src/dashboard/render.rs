@@ -0,0 +1,200 @@
+use std::collections::{HashMap, VecDeque};
+use robotics_lib::event::events::Event;
+use robotics_lib::world::tile::Content::{Bin, Fire, Garbage};
+use robotics_lib::world::tile::{Tile, TileType};
+use crate::state::AiState;
+
+/// Number of events kept in the event log
+const MAX_EVENTS: usize = 100;
+
+/// Accumulates the data of each tick and renders it as HTML fragments for the dashboard page
+///
+/// Every fragment has the id of the element it replaces, so they can be swapped by the htmx websocket extension
+pub struct DashboardView {
+    tick: usize,
+    known_tiles: HashMap<(usize, usize), Tile>,
+    events: VecDeque<String>,
+}
+
+impl DashboardView {
+    pub fn new() -> DashboardView {
+        DashboardView {
+            tick: 0,
+            known_tiles: HashMap::new(),
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Stores the result of a process tick
+    pub fn update(&mut self, events: &[Event], discovered_tiles: &[(Tile, (usize, usize))]) {
+        for (tile, coordinates) in discovered_tiles {
+            self.known_tiles.insert(*coordinates, tile.clone());
+        }
+
+        for event in events {
+            self.events.push_front(format!("[{}] {:?}", self.tick, event));
+        }
+        self.events.truncate(MAX_EVENTS);
+
+        self.tick += 1;
+    }
+
+    /// Renders all the fragments of the page
+    pub fn render(&self, state: &AiState) -> String {
+        format!(
+            "{}{}{}{}",
+            self.render_map(state),
+            self.render_robot(state),
+            self.render_tasks(state),
+            self.render_events()
+        )
+    }
+
+    /// Renders the bounding box of the known tiles and the robot, so that the whole explored map is visible
+    fn render_map(&self, state: &AiState) -> String {
+        let (robot_row, robot_col) = state.robot_coordinates;
+        let ((min_row, min_col), (max_row, max_col)) = self.known_bounds(state.robot_coordinates);
+        let mut map = String::new();
+
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                let tile = self.known_tiles.get(&(row, col));
+                let tile_class = tile.map_or("t-unknown", |tile| tile_type_class(&tile.tile_type));
+
+                let (content_class, symbol) = if (row, col) == (robot_row, robot_col) {
+                    ("c-robot", '@')
+                } else {
+                    match tile.map(|tile| &tile.content) {
+                        Some(Fire) => ("c-fire", 'F'),
+                        Some(Garbage(_)) => ("c-garbage", 'G'),
+                        Some(Bin(_)) => ("c-bin", 'B'),
+                        _ => match tile.map(|tile| &tile.tile_type) {
+                            Some(TileType::Teleport(_)) => ("", 'T'),
+                            _ => ("", ' '),
+                        },
+                    }
+                };
+
+                map.push_str(&format!(
+                    "<span class=\"{} {}\">{}</span>",
+                    tile_class, content_class, symbol
+                ));
+            }
+            map.push('\n');
+        }
+
+        format!("<pre id=\"map\" hx-swap-oob=\"true\">{}</pre>", map)
+    }
+
+    /// The top left and bottom right corners of the rectangle containing the known tiles and the robot
+    fn known_bounds(&self, robot_coordinates: (usize, usize)) -> ((usize, usize), (usize, usize)) {
+        self.known_tiles.keys().fold(
+            (robot_coordinates, robot_coordinates),
+            |((min_row, min_col), (max_row, max_col)), (row, col)| {
+                ((min_row.min(*row), min_col.min(*col)), (max_row.max(*row), max_col.max(*col)))
+            },
+        )
+    }
+
+    fn render_robot(&self, state: &AiState) -> String {
+        let backpack: Vec<String> = state
+            .backpack
+            .iter()
+            .filter(|(_, amount)| **amount > 0)
+            .map(|(content, amount)| format!("{:?}: {}", content, amount))
+            .collect();
+
+        format!(
+            "<div id=\"robot\" hx-swap-oob=\"true\">Tick: {}<br>Position: {:?}<br>Energy: {}<br>Resting: {}<br>Backpack: {}<br>Terminated: {}</div>",
+            self.tick,
+            state.robot_coordinates,
+            state.energy,
+            state.resting,
+            escape_html(&backpack.join(", ")),
+            state.terminate
+        )
+    }
+
+    fn render_tasks(&self, state: &AiState) -> String {
+        let current = match &state.current_task {
+            None => "none".to_string(),
+            Some(task) => format!("{:?} at {:?}", task.action, task.coordinates),
+        };
+
+        let pending: String = state
+            .pending_tasks
+            .iter()
+            .map(|task| format!("<li>{} - {:?} at {:?}</li>", task.priority, task.action, task.coordinates))
+            .collect();
+
+        format!(
+            "<div id=\"tasks\" hx-swap-oob=\"true\">Current: {}<ol>{}</ol></div>",
+            current, pending
+        )
+    }
+
+    fn render_events(&self) -> String {
+        let events: String = self
+            .events
+            .iter()
+            .map(|event| format!("<div>{}</div>", escape_html(event)))
+            .collect();
+
+        format!("<div id=\"events\" hx-swap-oob=\"true\">{}</div>", events)
+    }
+}
+
+impl Default for DashboardView {
+    fn default() -> Self {
+        DashboardView::new()
+    }
+}
+
+/// CSS class used to color a tile type
+fn tile_type_class(tile_type: &TileType) -> &'static str {
+    match tile_type {
+        TileType::DeepWater => "t-deep-water",
+        TileType::ShallowWater => "t-shallow-water",
+        TileType::Sand => "t-sand",
+        TileType::Grass => "t-grass",
+        TileType::Street => "t-street",
+        TileType::Hill => "t-hill",
+        TileType::Mountain => "t-mountain",
+        TileType::Snow => "t-snow",
+        TileType::Lava => "t-lava",
+        TileType::Teleport(_) => "t-teleport",
+        TileType::Wall => "t-wall",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_map_covers_every_known_tile() {
+        let tile = Tile {
+            tile_type: TileType::Grass,
+            content: Fire,
+            elevation: 0,
+        };
+        let mut view = DashboardView::new();
+        view.update(&[], &[(tile.clone(), (2, 40)), (tile, (30, 5))]);
+
+        let mut state = AiState::new();
+        state.robot_coordinates = (10, 10);
+
+        assert_eq!(view.known_bounds(state.robot_coordinates), ((2, 5), (30, 40)));
+
+        let map = view.render_map(&state);
+        assert_eq!(map.matches('\n').count(), 29);
+        assert_eq!(map.matches("c-fire").count(), 2);
+        assert_eq!(map.matches("c-robot").count(), 1);
+    }
+}
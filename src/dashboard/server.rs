@@ -0,0 +1,154 @@
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use log::{debug, error, info};
+use tungstenite::{Message, WebSocket};
+
+/// Page served to browsers, it receives the HTML fragments of each tick through a websocket
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// Script of the page, served by the dashboard itself so that it works without an internet connection
+const DASHBOARD_JS: &str = include_str!("dashboard.js");
+
+/// Longest time a request can take to arrive, and a message can take to be sent before the browser is dropped
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest request line and headers accepted
+const MAX_REQUEST_HEAD: usize = 8192;
+
+/// Serves the dashboard page and pushes updates to every connected browser over websockets
+///
+/// Both the page and the websocket are served on the same address, connections are accepted on a background thread
+/// and each one is handled on its own thread
+pub struct DashboardServer {
+    clients: Arc<Mutex<Vec<WebSocket<TcpStream>>>>,
+}
+
+impl DashboardServer {
+    /// Starts listening on `address`, e.g. `127.0.0.1:8080`
+    pub fn start(address: &str) -> std::io::Result<DashboardServer> {
+        let listener = TcpListener::bind(address)?;
+        let clients = Arc::new(Mutex::new(vec![]));
+
+        info!("Dashboard available at http://{}", listener.local_addr()?);
+
+        let accepted_clients = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let clients = accepted_clients.clone();
+                        thread::spawn(move || Self::handle_connection(stream, &clients));
+                    }
+                    Err(e) => error!("Failed accepting dashboard connection: {:?}", e),
+                }
+            }
+        });
+
+        Ok(DashboardServer { clients })
+    }
+
+    /// Sends `message` to every connected browser, dropping the ones that disconnected or are too slow to receive it
+    ///
+    /// The clients are taken out of the list while sending, so that a slow browser doesn't block the ones connecting meanwhile
+    pub fn broadcast(&self, message: &str) {
+        let mut clients = std::mem::take(&mut *self.clients.lock().unwrap());
+        let message = Message::Text(message.to_string());
+
+        clients.retain_mut(|client| match client.send(message.clone()) {
+            Ok(_) => true,
+            Err(e) => {
+                debug!("Dropping dashboard client: {:?}", e);
+                false
+            }
+        });
+
+        // Browsers that connected while sending receive the next message
+        let mut connected = self.clients.lock().unwrap();
+        clients.append(&mut connected);
+        *connected = clients;
+    }
+
+    /// Upgrades websocket requests and stores them as clients, answers every other request with the page or its script
+    fn handle_connection(mut stream: TcpStream, clients: &Arc<Mutex<Vec<WebSocket<TcpStream>>>>) {
+        // Slow browsers can't block the broadcasts
+        let timeouts = stream
+            .set_read_timeout(Some(SOCKET_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(SOCKET_TIMEOUT)));
+        if let Err(e) = timeouts {
+            error!("Failed configuring dashboard connection: {:?}", e);
+            return;
+        }
+
+        let head = match Self::peek_request_head(&stream) {
+            Ok(head) => head,
+            Err(e) => {
+                error!("Failed reading dashboard request: {:?}", e);
+                return;
+            }
+        };
+
+        if head.to_lowercase().contains("upgrade: websocket") {
+            match tungstenite::accept(stream) {
+                Ok(websocket) => clients.lock().unwrap().push(websocket),
+                Err(e) => error!("Failed websocket handshake: {:?}", e),
+            }
+            return;
+        }
+
+        // The request was only peeked, it's consumed before answering
+        let mut request = vec![0; head.len()];
+        if let Err(e) = stream.read_exact(&mut request) {
+            error!("Failed reading dashboard request: {:?}", e);
+            return;
+        }
+
+        let path = head.split_whitespace().nth(1).unwrap_or("/");
+        let (content_type, body) = match path {
+            "/dashboard.js" => ("application/javascript", DASHBOARD_JS),
+            _ => ("text/html", INDEX_HTML),
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            content_type,
+            body.len(),
+            body
+        );
+
+        if let Err(e) = stream.write_all(response.as_bytes()) {
+            error!("Failed serving dashboard {}: {:?}", path, e);
+        }
+    }
+
+    /// Returns the request line and the headers without consuming them, so that the websocket handshake can still read them
+    ///
+    /// Waits until the blank line ending the headers has arrived, fails if it takes longer than `SOCKET_TIMEOUT`
+    fn peek_request_head(stream: &TcpStream) -> std::io::Result<String> {
+        let mut buffer = vec![0; MAX_REQUEST_HEAD];
+        let deadline = Instant::now() + SOCKET_TIMEOUT;
+
+        loop {
+            let read = stream.peek(&mut buffer)?;
+
+            if let Some(end) = buffer[..read].windows(4).position(|window| window == b"\r\n\r\n") {
+                return Ok(String::from_utf8_lossy(&buffer[..end + 4]).into_owned());
+            }
+
+            if read == 0 {
+                return Err(Error::new(ErrorKind::UnexpectedEof, "connection closed during the request"));
+            }
+            if read == buffer.len() {
+                return Err(Error::new(ErrorKind::InvalidData, "request headers too long"));
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::new(ErrorKind::TimedOut, "request not completed in time"));
+            }
+
+            // The rest of the request hasn't arrived yet
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::task::TaskAction;
+use crate::strategies::detection::Scan;
+
+/// Decides how much energy the robot keeps aside and when it rests to regenerate it
+///
+/// Before moving the robot checks that it can reach the current task and perform its action without going below
+/// the `reserve`, otherwise it rests: it skips scanning and moving, letting the energy regenerate over the process ticks.
+///
+/// Properties:
+/// - reserve: energy that is never committed to a task, kept for scanning and for costs that weren't predicted
+/// - destroy_cost: estimated energy consumed by `destroy`
+/// - put_cost: estimated energy consumed by `put`
+/// - view_cost: estimated energy consumed by looking around
+/// - one_direction_cost: estimated energy consumed by looking in one direction, for each tile of distance
+/// - max_energy: the maximum energy level of the robot, the required energy is capped to it so that the robot doesn't rest forever
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnergyPolicy {
+    pub reserve: usize,
+    pub destroy_cost: usize,
+    pub put_cost: usize,
+    pub view_cost: usize,
+    pub one_direction_cost: usize,
+    pub max_energy: usize,
+}
+
+impl EnergyPolicy {
+    /// Estimated energy consumed by performing the action of a task
+    pub fn action_cost(&self, action: &TaskAction) -> usize {
+        match action {
+            TaskAction::PutGarbageInBin => self.put_cost,
+            TaskAction::DestroyFire | TaskAction::DestroyGarbage => self.destroy_cost,
+        }
+    }
+
+    /// Estimated energy consumed by a scan, also used to choose the scan of a process tick
+    pub fn scan_cost(&self, scan: &Scan) -> usize {
+        match scan {
+            Scan::Around => self.view_cost,
+            Scan::OneDirection(_, distance) => self.one_direction_cost * distance,
+        }
+    }
+
+    /// Energy the robot needs to have in order to spend `cost` while keeping the reserve
+    pub fn required_energy(&self, cost: usize) -> usize {
+        (cost + self.reserve).min(self.max_energy)
+    }
+}
+
+impl Default for EnergyPolicy {
+    fn default() -> Self {
+        EnergyPolicy {
+            reserve: 50,
+            destroy_cost: 10,
+            put_cost: 10,
+            view_cost: 3,
+            one_direction_cost: 3,
+            max_energy: 1000,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{Display, Formatter};
+use robotics_lib::utils::LibError;
+use crate::models::task::{TaskFailure, TaskSnapshot};
+
+/// Maximum number of errors kept in `AiState::last_errors`
+pub const MAX_LAST_ERRORS: usize = 20;
+
+/// Stage of the process tick in which an error occurred
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AiStage {
+    /// Looking around to discover tiles
+    Scan,
+    /// Teleporting along a planned path or while exploring
+    Teleport,
+    /// Moving towards a frontier while exploring
+    Exploration,
+    /// Moving towards the current task
+    Navigation,
+    /// Performing the action of the current task
+    Action,
+}
+
+/// Error occurred while the ai was performing an operation
+#[derive(Debug)]
+pub enum AiError {
+    /// `robotics_lib` returned an error
+    ///
+    /// Properties:
+    /// - error: the error returned by `robotics_lib`
+    /// - stage: what the robot was doing
+    /// - task: the task the robot was performing, if any
+    /// - coordinates: the coordinates of the robot, in the form of row, col
+    Lib {
+        error: LibError,
+        stage: AiStage,
+        task: Option<TaskSnapshot>,
+        coordinates: (usize, usize),
+    },
+    /// No path from the robot to the task could be found
+    ///
+    /// Properties:
+    /// - task: the unreachable task
+    /// - coordinates: the coordinates of the robot, in the form of row, col
+    Unreachable {
+        task: TaskSnapshot,
+        coordinates: (usize, usize),
+    },
+    /// An operation on the current task was requested but there is none
+    NoCurrentTask,
+}
+
+impl AiError {
+    /// The stage in which the error occurred
+    pub fn stage(&self) -> AiStage {
+        match self {
+            AiError::Lib { stage, .. } => *stage,
+            AiError::Unreachable { .. } | AiError::NoCurrentTask => AiStage::Navigation,
+        }
+    }
+
+    /// The task the robot was performing when the error occurred, if any
+    pub fn task(&self) -> Option<&TaskSnapshot> {
+        match self {
+            AiError::Lib { task, .. } => task.as_ref(),
+            AiError::Unreachable { task, .. } => Some(task),
+            AiError::NoCurrentTask => None,
+        }
+    }
+}
+
+impl Display for AiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AiError::Lib { error, stage, task, coordinates } => {
+                write!(f, "{:?} failed at ({}, {}): {:?}", stage, coordinates.0, coordinates.1, error)?;
+
+                match task {
+                    Some(task) => write!(
+                        f,
+                        " while performing {:?} at ({}, {})",
+                        task.action, task.coordinates.0, task.coordinates.1
+                    ),
+                    None => Ok(()),
+                }
+            }
+            AiError::Unreachable { task, coordinates } => write!(
+                f,
+                "no path from ({}, {}) to {:?} at ({}, {})",
+                coordinates.0, coordinates.1, task.action, task.coordinates.0, task.coordinates.1
+            ),
+            AiError::NoCurrentTask => write!(f, "there is no current task"),
+        }
+    }
+}
+
+impl std::error::Error for AiError {}
+
+impl From<&AiError> for TaskFailure {
+    fn from(error: &AiError) -> Self {
+        match error {
+            AiError::Lib { error, .. } => TaskFailure::from(error),
+            AiError::Unreachable { .. } => TaskFailure::Unreachable,
+            AiError::NoCurrentTask => TaskFailure::Other(error.to_string()),
+        }
+    }
+}
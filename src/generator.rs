@@ -0,0 +1,123 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use robotics_lib::world::environmental_conditions::{EnvironmentalConditions, WeatherType};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+use robotics_lib::world::world_generator::{Generator, World};
+
+/// Score that can be reached in the generated worlds
+const MAX_SCORE: f32 = 1000.0;
+
+/// Generates a square world of random tiles with some fire, garbage, bins and teleports, without showing any GUI
+///
+/// Used by `WrapperTrashinatorRobotBuilder::world_size`, so that tests and headless runs can choose the size of the world
+///
+/// Properties:
+/// - size: number of rows and columns of the world, at least 1
+/// - seed: seed of the RNG choosing the tiles, the same seed generates the same world
+pub struct RandomWorldGenerator {
+    pub size: usize,
+    pub seed: u64,
+}
+
+impl RandomWorldGenerator {
+    pub fn new(size: usize, seed: u64) -> RandomWorldGenerator {
+        RandomWorldGenerator { size, seed }
+    }
+}
+
+impl Generator for RandomWorldGenerator {
+    fn gen(&mut self) -> World {
+        let size = self.size.max(1);
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let mut map: Vec<Vec<Tile>> = (0..size)
+            .map(|_| (0..size).map(|_| random_tile(&mut rng)).collect())
+            .collect();
+
+        // The robot spawns on an empty walkable tile, the middle one is turned into grass if there is none
+        let spawn_candidates: Vec<(usize, usize)> = (0..size)
+            .flat_map(|row| (0..size).map(move |col| (row, col)))
+            .filter(|(row, col)| {
+                let tile = &map[*row][*col];
+                tile.tile_type.properties().walk() && tile.content == Content::None
+            })
+            .collect();
+
+        let spawn = if spawn_candidates.is_empty() {
+            map[size / 2][size / 2] = Tile {
+                tile_type: TileType::Grass,
+                content: Content::None,
+                elevation: 0,
+            };
+            (size / 2, size / 2)
+        } else {
+            spawn_candidates[rng.gen_range(0..spawn_candidates.len())]
+        };
+
+        let environmental_conditions =
+            EnvironmentalConditions::new(&[WeatherType::Sunny, WeatherType::Rainy], 15, 12)
+                .expect("Invalid environmental conditions");
+
+        (map, spawn, environmental_conditions, MAX_SCORE, None)
+    }
+}
+
+/// A random tile, contents are only placed on land that can be walked on
+fn random_tile(rng: &mut StdRng) -> Tile {
+    let tile_type = match rng.gen_range(0..100) {
+        0..=44 => TileType::Grass,
+        45..=54 => TileType::Sand,
+        55..=62 => TileType::Hill,
+        63..=67 => TileType::Street,
+        68..=75 => TileType::ShallowWater,
+        76..=85 => TileType::DeepWater,
+        86..=91 => TileType::Mountain,
+        92..=95 => TileType::Snow,
+        96..=98 => TileType::Lava,
+        _ => TileType::Teleport(false),
+    };
+
+    let has_land_content = matches!(
+        tile_type,
+        TileType::Grass | TileType::Sand | TileType::Hill | TileType::Street
+    );
+
+    let content = match rng.gen_range(0..100) {
+        0..=2 if has_land_content => Content::Fire,
+        3..=7 if has_land_content => Content::Garbage(rng.gen_range(1..=3)),
+        8 if has_land_content => Content::Bin(0..10),
+        _ => Content::None,
+    };
+
+    Tile {
+        tile_type,
+        content,
+        elevation: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_a_world_of_the_given_size() {
+        let (map, spawn, ..) = RandomWorldGenerator::new(12, 7).gen();
+
+        assert_eq!(map.len(), 12);
+        assert!(map.iter().all(|row| row.len() == 12));
+
+        let spawn_tile = &map[spawn.0][spawn.1];
+        assert!(spawn_tile.tile_type.properties().walk());
+        assert_eq!(spawn_tile.content, Content::None);
+    }
+
+    #[test]
+    fn the_same_seed_generates_the_same_world() {
+        let (map, spawn, ..) = RandomWorldGenerator::new(8, 3).gen();
+        let (same_map, same_spawn, ..) = RandomWorldGenerator::new(8, 3).gen();
+
+        assert_eq!(map, same_map);
+        assert_eq!(spawn, same_spawn);
+    }
+}
@@ -0,0 +1,186 @@
+/// Condition that makes the robot terminate
+///
+/// Goals can be combined with `and` and `or`, e.g.
+/// `Goal::ExtinguishAllKnownFires.and(Goal::ExploreWorld(50.0)).or(Goal::Ticks(10_000))`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Goal {
+    /// Complete the given number of tasks of any kind
+    TasksCompleted(usize),
+    /// No known fire is left to extinguish, once at least a fire was extinguished or every reachable tile was explored
+    ExtinguishAllKnownFires,
+    /// Collect at least the given amount of garbage
+    CollectGarbage(usize),
+    /// Put at least the given amount of garbage in bins
+    DepositInBins(usize),
+    /// Discover at least the given percentage of the world, from 0 to 100
+    ExploreWorld(f64),
+    /// Perform the given number of process ticks
+    Ticks(usize),
+    /// The energy of the robot is below the given level, `EnergyBelow(1)` stops when the energy is depleted
+    EnergyBelow(usize),
+    /// All the goals must be met
+    All(Vec<Goal>),
+    /// At least one of the goals must be met
+    Any(Vec<Goal>),
+}
+
+/// What the robot has achieved so far, used to check the goals
+pub(crate) struct GoalProgress {
+    pub(crate) tasks_completed: usize,
+    pub(crate) known_fires: usize,
+    pub(crate) garbage_collected: usize,
+    pub(crate) garbage_deposited: usize,
+    pub(crate) fires_extinguished: usize,
+    /// Percentage of the world discovered, from 0 to 100
+    pub(crate) explored: f64,
+    /// Whether the robot ran out of reachable frontiers at least once
+    pub(crate) exploration_complete: bool,
+    pub(crate) ticks: usize,
+    pub(crate) energy: usize,
+}
+
+impl Goal {
+    /// Combines the goals so that both must be met
+    pub fn and(self, other: Goal) -> Goal {
+        match self {
+            Goal::All(mut goals) => {
+                goals.push(other);
+                Goal::All(goals)
+            }
+            goal => Goal::All(vec![goal, other]),
+        }
+    }
+
+    /// Combines the goals so that at least one must be met
+    pub fn or(self, other: Goal) -> Goal {
+        match self {
+            Goal::Any(mut goals) => {
+                goals.push(other);
+                Goal::Any(goals)
+            }
+            goal => Goal::Any(vec![goal, other]),
+        }
+    }
+
+    /// Checks the goal against the progress of the robot
+    ///
+    /// Returns the simple goals (neither `All` nor `Any`) that made the goal met, or `None` if it isn't met
+    pub(crate) fn met_goals(&self, progress: &GoalProgress) -> Option<Vec<Goal>> {
+        let is_met = match self {
+            Goal::TasksCompleted(tasks) => progress.tasks_completed >= *tasks,
+            // Without any fire extinguished or a complete exploration there might just be no fire discovered yet
+            Goal::ExtinguishAllKnownFires => {
+                progress.known_fires == 0 && (progress.fires_extinguished > 0 || progress.exploration_complete)
+            }
+            Goal::CollectGarbage(amount) => progress.garbage_collected >= *amount,
+            Goal::DepositInBins(amount) => progress.garbage_deposited >= *amount,
+            Goal::ExploreWorld(percentage) => progress.explored >= *percentage,
+            Goal::Ticks(ticks) => progress.ticks >= *ticks,
+            Goal::EnergyBelow(energy) => progress.energy < *energy,
+            Goal::All(goals) => {
+                let mut met = vec![];
+
+                for goal in goals {
+                    met.extend(goal.met_goals(progress)?);
+                }
+
+                return Some(met);
+            }
+            Goal::Any(goals) => return goals.iter().find_map(|goal| goal.met_goals(progress)),
+        };
+
+        if is_met {
+            Some(vec![self.clone()])
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress() -> GoalProgress {
+        GoalProgress {
+            tasks_completed: 0,
+            known_fires: 0,
+            garbage_collected: 0,
+            garbage_deposited: 0,
+            fires_extinguished: 0,
+            explored: 0.0,
+            exploration_complete: false,
+            ticks: 0,
+            energy: 1000,
+        }
+    }
+
+    #[test]
+    fn simple_goals() {
+        let mut progress = progress();
+        progress.tasks_completed = 3;
+        progress.garbage_collected = 5;
+        progress.explored = 50.0;
+        progress.ticks = 10;
+
+        assert_eq!(Goal::TasksCompleted(3).met_goals(&progress), Some(vec![Goal::TasksCompleted(3)]));
+        assert_eq!(Goal::TasksCompleted(4).met_goals(&progress), None);
+        assert!(Goal::CollectGarbage(5).met_goals(&progress).is_some());
+        assert!(Goal::DepositInBins(1).met_goals(&progress).is_none());
+        assert!(Goal::ExploreWorld(50.0).met_goals(&progress).is_some());
+        assert!(Goal::ExploreWorld(50.5).met_goals(&progress).is_none());
+        assert!(Goal::Ticks(10).met_goals(&progress).is_some());
+        assert!(Goal::EnergyBelow(1000).met_goals(&progress).is_none());
+        assert!(Goal::EnergyBelow(1001).met_goals(&progress).is_some());
+    }
+
+    #[test]
+    fn extinguish_all_known_fires_needs_a_fire_or_a_complete_exploration() {
+        let mut progress = progress();
+        assert!(Goal::ExtinguishAllKnownFires.met_goals(&progress).is_none());
+
+        progress.fires_extinguished = 1;
+        assert!(Goal::ExtinguishAllKnownFires.met_goals(&progress).is_some());
+
+        progress.known_fires = 2;
+        assert!(Goal::ExtinguishAllKnownFires.met_goals(&progress).is_none());
+
+        progress.known_fires = 0;
+        progress.fires_extinguished = 0;
+        progress.exploration_complete = true;
+        assert!(Goal::ExtinguishAllKnownFires.met_goals(&progress).is_some());
+    }
+
+    #[test]
+    fn combinators_flatten_and_report_the_met_goals() {
+        let goal = Goal::TasksCompleted(1).and(Goal::Ticks(5)).and(Goal::CollectGarbage(2));
+        assert_eq!(
+            goal,
+            Goal::All(vec![Goal::TasksCompleted(1), Goal::Ticks(5), Goal::CollectGarbage(2)])
+        );
+
+        let either = Goal::Ticks(100).or(Goal::EnergyBelow(10));
+        assert_eq!(either, Goal::Any(vec![Goal::Ticks(100), Goal::EnergyBelow(10)]));
+
+        let mut progress = progress();
+        progress.tasks_completed = 1;
+        progress.ticks = 5;
+        assert_eq!(goal.met_goals(&progress), None);
+        assert_eq!(either.met_goals(&progress), None);
+
+        progress.garbage_collected = 2;
+        progress.energy = 5;
+        assert_eq!(
+            goal.met_goals(&progress),
+            Some(vec![Goal::TasksCompleted(1), Goal::Ticks(5), Goal::CollectGarbage(2)])
+        );
+        assert_eq!(either.met_goals(&progress), Some(vec![Goal::EnergyBelow(10)]));
+
+        // Nested combinations report only the simple goals
+        let nested = Goal::Ticks(100).or(Goal::TasksCompleted(1).and(Goal::EnergyBelow(10)));
+        assert_eq!(
+            nested.met_goals(&progress),
+            Some(vec![Goal::TasksCompleted(1), Goal::EnergyBelow(10)])
+        );
+    }
+}
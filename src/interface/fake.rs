@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use robotics_lib::interface::Direction;
+use robotics_lib::utils::LibError;
+use robotics_lib::world::tile::Content::{Bin, Fire, Garbage};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+use crate::interface::{MovementResult, RobotStatus, WorldInterface};
+use crate::navigation::coordinates::{offset, step, DirectionExt};
+use crate::navigation::cost::TELEPORT_COST;
+use crate::robot::TrashinatorRobot;
+
+/// An interaction with a `FakeWorld`, recorded to check what the ai did
+#[derive(Clone, Debug, PartialEq)]
+pub enum FakeCall {
+    ViewAround,
+    OneDirectionView(Direction, usize),
+    Go(Direction),
+    Teleport((usize, usize)),
+    Destroy(Direction),
+    Put(Content, usize, Direction),
+}
+
+/// An in-memory world made of a hand-built grid of tiles, used to test the ai without `robotics_lib`
+///
+/// The robot is simulated by the world itself: its position, energy and backpack are scripted in `status`
+/// and updated by the actions, the robot passed to the actions is ignored.
+/// Walking costs like in `robotics_lib`, every other action costs `action_cost`.
+///
+/// Properties:
+/// - tiles: the square grid of tiles, indexed by row and col
+/// - status: the position, energy and backpack of the robot
+/// - action_cost: energy consumed by the actions other than walking and teleporting
+/// - calls: every interaction of the ai with the world, in order
+pub struct FakeWorld {
+    pub tiles: Vec<Vec<Tile>>,
+    pub status: RobotStatus,
+    pub action_cost: usize,
+    pub calls: Vec<FakeCall>,
+}
+
+impl FakeWorld {
+    /// Creates a world from a grid of tiles with the robot at `position`, full energy and an empty backpack
+    pub fn new(tiles: Vec<Vec<Tile>>, position: (usize, usize)) -> FakeWorld {
+        FakeWorld {
+            tiles,
+            status: RobotStatus {
+                position,
+                energy: 1000,
+                backpack: HashMap::new(),
+                backpack_size: 20,
+            },
+            action_cost: 1,
+            calls: vec![],
+        }
+    }
+
+    /// Creates a world of `size` by `size` grass tiles with the robot at `position`
+    pub fn grass(size: usize, position: (usize, usize)) -> FakeWorld {
+        let tile = Tile {
+            tile_type: TileType::Grass,
+            content: Content::None,
+            elevation: 0,
+        };
+
+        FakeWorld::new(vec![vec![tile; size]; size], position)
+    }
+
+    /// Replaces the content of the tile at the given coordinates
+    pub fn set_content(&mut self, coordinates: (usize, usize), content: Content) {
+        self.tiles[coordinates.0][coordinates.1].content = content;
+    }
+
+    /// Replaces the type of the tile at the given coordinates
+    pub fn set_tile_type(&mut self, coordinates: (usize, usize), tile_type: TileType) {
+        self.tiles[coordinates.0][coordinates.1].tile_type = tile_type;
+    }
+
+    pub fn tile(&self, coordinates: (usize, usize)) -> Option<&Tile> {
+        self.tiles.get(coordinates.0).and_then(|row| row.get(coordinates.1))
+    }
+
+    /// The coordinates of the tile next to the robot in the direction, if it's inside the world
+    fn target(&self, direction: &Direction) -> Option<(usize, usize)> {
+        step(self.status.position, direction).filter(|coordinates| self.tile(*coordinates).is_some())
+    }
+
+    fn consume_energy(&mut self, amount: usize) -> Result<(), LibError> {
+        if self.status.energy < amount {
+            return Err(LibError::NotEnoughEnergy);
+        }
+
+        self.status.energy -= amount;
+        Ok(())
+    }
+
+    /// The 3x3 view centered on `coordinates`, tiles outside of the world are `None`
+    fn view_at(&self, coordinates: (usize, usize)) -> Vec<Vec<Option<Tile>>> {
+        (-1..=1)
+            .map(|row| {
+                (-1..=1)
+                    .map(|col| offset(coordinates, (row, col)).and_then(|tile| self.tile(tile)).cloned())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Moves the robot, consuming the energy to walk there
+    fn move_to(&mut self, coordinates: (usize, usize), cost: usize) -> MovementResult {
+        self.consume_energy(cost)?;
+        self.status.position = coordinates;
+
+        Ok((self.view_at(coordinates), coordinates))
+    }
+}
+
+impl WorldInterface for FakeWorld {
+    fn status(&self, _robot: &TrashinatorRobot) -> RobotStatus {
+        self.status.clone()
+    }
+
+    fn robot_map(&mut self) -> Option<Vec<Vec<Option<Tile>>>> {
+        Some(
+            self.tiles
+                .iter()
+                .map(|row| row.iter().cloned().map(Some).collect())
+                .collect(),
+        )
+    }
+
+    fn view_around(&mut self, _robot: &mut TrashinatorRobot) -> Vec<(Tile, (usize, usize))> {
+        self.calls.push(FakeCall::ViewAround);
+
+        if self.consume_energy(self.action_cost).is_err() {
+            return vec![];
+        }
+
+        let position = self.status.position;
+        (-1..=1)
+            .flat_map(|row| (-1..=1).map(move |col| (row, col)))
+            .filter_map(|delta| offset(position, delta))
+            .filter_map(|coordinates| self.tile(coordinates).map(|tile| (tile.clone(), coordinates)))
+            .collect()
+    }
+
+    fn one_direction_view(
+        &mut self,
+        _robot: &mut TrashinatorRobot,
+        direction: Direction,
+        distance: usize,
+    ) -> Result<Vec<Vec<Tile>>, LibError> {
+        self.calls.push(FakeCall::OneDirectionView(direction.clone(), distance));
+        self.consume_energy(self.action_cost)?;
+
+        let position = self.status.position;
+        let forward = direction.delta();
+        let across = match direction {
+            Direction::Up | Direction::Down => (0, 1),
+            Direction::Left | Direction::Right => (1, 0),
+        };
+        let tile_at = |steps: i64, side: i64| {
+            offset(position, (forward.0 * steps + across.0 * side, forward.1 * steps + across.1 * side))
+                .and_then(|coordinates| self.tile(coordinates))
+                .cloned()
+        };
+
+        // Same layout as `robotics_lib`: rows at increasing distance looking up or down, rows next to the robot otherwise
+        let view = match direction {
+            Direction::Up | Direction::Down => (1..=distance as i64)
+                .map(|steps| (-1..=1).filter_map(|side| tile_at(steps, side)).collect::<Vec<_>>())
+                .filter(|row| !row.is_empty())
+                .collect(),
+            Direction::Left | Direction::Right => (-1..=1)
+                .map(|side| (1..=distance as i64).filter_map(|steps| tile_at(steps, side)).collect::<Vec<_>>())
+                .filter(|row| !row.is_empty())
+                .collect(),
+        };
+
+        Ok(view)
+    }
+
+    fn go(&mut self, _robot: &mut TrashinatorRobot, direction: Direction) -> MovementResult {
+        self.calls.push(FakeCall::Go(direction.clone()));
+
+        let coordinates = self.target(&direction).ok_or(LibError::OutOfBounds)?;
+        let (from, to) = (&self.tiles[self.status.position.0][self.status.position.1], &self.tiles[coordinates.0][coordinates.1]);
+
+        if !to.tile_type.properties().walk() {
+            return Err(LibError::CannotWalk);
+        }
+
+        let climb = to.elevation.saturating_sub(from.elevation);
+        let cost = to.tile_type.properties().cost() + climb.pow(2);
+
+        self.move_to(coordinates, cost)
+    }
+
+    fn teleport(&mut self, _robot: &mut TrashinatorRobot, coordinates: (usize, usize)) -> MovementResult {
+        self.calls.push(FakeCall::Teleport(coordinates));
+
+        let is_teleport = |tile: Option<&Tile>| matches!(tile.map(|tile| &tile.tile_type), Some(TileType::Teleport(true)));
+        if !is_teleport(self.tile(self.status.position)) || !is_teleport(self.tile(coordinates)) {
+            return Err(LibError::OperationNotAllowed);
+        }
+
+        self.move_to(coordinates, TELEPORT_COST)
+    }
+
+    fn destroy(&mut self, _robot: &mut TrashinatorRobot, direction: Direction) -> Result<usize, LibError> {
+        self.calls.push(FakeCall::Destroy(direction.clone()));
+
+        let coordinates = self.target(&direction).ok_or(LibError::OutOfBounds)?;
+        let content = self.tiles[coordinates.0][coordinates.1].content.clone();
+
+        let collected = match content {
+            Fire => 0,
+            Garbage(amount) => {
+                let used: usize = self.status.backpack.values().sum();
+                if used + amount > self.status.backpack_size {
+                    return Err(LibError::NotEnoughSpace(amount));
+                }
+
+                amount
+            }
+            _ => return Err(LibError::NoContent),
+        };
+
+        self.consume_energy(self.action_cost)?;
+        self.tiles[coordinates.0][coordinates.1].content = Content::None;
+
+        if collected > 0 {
+            *self.status.backpack.entry(Garbage(0)).or_insert(0) += collected;
+        }
+
+        Ok(collected)
+    }
+
+    fn put(
+        &mut self,
+        _robot: &mut TrashinatorRobot,
+        content: Content,
+        quantity: usize,
+        direction: Direction,
+    ) -> Result<usize, LibError> {
+        self.calls.push(FakeCall::Put(content.clone(), quantity, direction.clone()));
+
+        let coordinates = self.target(&direction).ok_or(LibError::OutOfBounds)?;
+        let capacity = match &self.tiles[coordinates.0][coordinates.1].content {
+            Bin(range) => range.end.saturating_sub(range.start),
+            _ => return Err(LibError::OperationNotAllowed),
+        };
+
+        let in_backpack = self.status.backpack.get(&content).copied().unwrap_or(0);
+        let amount = quantity.min(in_backpack).min(capacity);
+        if amount == 0 {
+            return Err(LibError::NotEnoughSpace(quantity));
+        }
+
+        self.consume_energy(self.action_cost)?;
+        self.status.backpack.insert(content, in_backpack - amount);
+
+        if let Bin(range) = &mut self.tiles[coordinates.0][coordinates.1].content {
+            range.start += amount;
+        }
+
+        Ok(amount)
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fake;
+
+use std::collections::HashMap;
+use bob_lib::enhanced_map::{bob_view, BobMap};
+use robotics_lib::interface::{destroy, go, one_direction_view, put, robot_map, teleport, Direction};
+use robotics_lib::runner::Runnable;
+use robotics_lib::utils::LibError;
+use robotics_lib::world::tile::{Content, Tile};
+use robotics_lib::world::World;
+use crate::robot::TrashinatorRobot;
+
+/// The tiles around the robot after a movement, with the new coordinates of the robot
+pub type MovementResult = Result<(Vec<Vec<Option<Tile>>>, (usize, usize)), LibError>;
+
+/// What the ai knows about the body of the robot, read again after every interaction with the world
+///
+/// Properties:
+/// - position: the coordinates of the robot, in the form of row, col
+/// - energy: the energy level of the robot
+/// - backpack: the contents of the backpack
+/// - backpack_size: how many items fit in the backpack
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RobotStatus {
+    pub position: (usize, usize),
+    pub energy: usize,
+    pub backpack: HashMap<Content, usize>,
+    pub backpack_size: usize,
+}
+
+impl RobotStatus {
+    /// Reads the status of a robot living in a `robotics_lib` world
+    pub fn of(robot: &impl Runnable) -> RobotStatus {
+        let coordinates = robot.get_coordinate();
+
+        RobotStatus {
+            position: (coordinates.get_row(), coordinates.get_col()),
+            energy: robot.get_energy().get_energy_level(),
+            backpack: robot.get_backpack().get_contents().clone(),
+            backpack_size: robot.get_backpack().get_size(),
+        }
+    }
+}
+
+/// Every interaction of the ai with the world
+///
+/// Implemented by the `robotics_lib` `World`, calling the functions of its interface, and by `FakeWorld`, an in-memory
+/// world that allows testing the decisions of the ai on hand-built grids, available with the `test-utils` feature.
+/// The strategies receive it as a trait object, so that they run on both
+pub trait WorldInterface {
+    /// Reads the position, energy and backpack of the robot
+    fn status(&self, robot: &TrashinatorRobot) -> RobotStatus;
+
+    /// The map discovered by the robot, only its size is used
+    fn robot_map(&mut self) -> Option<Vec<Vec<Option<Tile>>>>;
+
+    /// Looks at the tiles around the robot, returning them with their coordinates
+    fn view_around(&mut self, robot: &mut TrashinatorRobot) -> Vec<(Tile, (usize, usize))>;
+
+    fn one_direction_view(
+        &mut self,
+        robot: &mut TrashinatorRobot,
+        direction: Direction,
+        distance: usize,
+    ) -> Result<Vec<Vec<Tile>>, LibError>;
+
+    fn go(&mut self, robot: &mut TrashinatorRobot, direction: Direction) -> MovementResult;
+
+    fn teleport(&mut self, robot: &mut TrashinatorRobot, coordinates: (usize, usize)) -> MovementResult;
+
+    fn destroy(&mut self, robot: &mut TrashinatorRobot, direction: Direction) -> Result<usize, LibError>;
+
+    fn put(
+        &mut self,
+        robot: &mut TrashinatorRobot,
+        content: Content,
+        quantity: usize,
+        direction: Direction,
+    ) -> Result<usize, LibError>;
+}
+
+impl WorldInterface for World {
+    fn status(&self, robot: &TrashinatorRobot) -> RobotStatus {
+        RobotStatus::of(robot)
+    }
+
+    fn robot_map(&mut self) -> Option<Vec<Vec<Option<Tile>>>> {
+        robot_map(self)
+    }
+
+    fn view_around(&mut self, robot: &mut TrashinatorRobot) -> Vec<(Tile, (usize, usize))> {
+        let mut bob_map = BobMap::init(self);
+
+        bob_view(robot, self, &mut bob_map)
+            .into_iter()
+            .flatten()
+            .filter_map(|(tile, row, col)| tile.map(|tile| (tile, (row, col))))
+            .collect()
+    }
+
+    fn one_direction_view(
+        &mut self,
+        robot: &mut TrashinatorRobot,
+        direction: Direction,
+        distance: usize,
+    ) -> Result<Vec<Vec<Tile>>, LibError> {
+        one_direction_view(robot, self, direction, distance)
+    }
+
+    fn go(&mut self, robot: &mut TrashinatorRobot, direction: Direction) -> MovementResult {
+        go(robot, self, direction)
+    }
+
+    fn teleport(&mut self, robot: &mut TrashinatorRobot, coordinates: (usize, usize)) -> MovementResult {
+        teleport(robot, self, coordinates)
+    }
+
+    fn destroy(&mut self, robot: &mut TrashinatorRobot, direction: Direction) -> Result<usize, LibError> {
+        destroy(robot, self, direction)
+    }
+
+    fn put(
+        &mut self,
+        robot: &mut TrashinatorRobot,
+        content: Content,
+        quantity: usize,
+        direction: Direction,
+    ) -> Result<usize, LibError> {
+        put(robot, self, content, quantity, direction)
+    }
+}
@@ -1,18 +1,40 @@
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
 use std::rc::Rc;
 use std::thread::sleep;
 use std::time::Duration;
+use log::error;
 use robotics_lib::event::events::Event;
-use robotics_lib::runner::{Robot, Runner};
+use robotics_lib::runner::Runner;
 use robotics_lib::world::tile::Tile;
-use worldgen_unwrap::public::WorldgeneratorUnwrap;
-use crate::robot::TrashinatorRobot;
+use robotics_lib::world::world_generator::Generator;
+use crate::builder::WrapperTrashinatorRobotBuilder;
+use crate::recording::recorder::TickRecorder;
+use crate::recording::{RecordingError, TickRecord};
+use crate::models::known_map::KnownMap;
+use crate::snapshot::KnownMapSnapshot;
 use crate::state::AiState;
+use crate::strategies::Strategies;
 
-mod models;
+pub mod background;
+pub mod builder;
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+pub mod energy;
+pub mod error;
+pub mod generator;
+pub mod goal;
+pub mod interface;
+pub mod models;
+mod navigation;
+pub mod recording;
 pub mod robot;
-mod state;
+pub mod snapshot;
+pub mod state;
 mod runnable;
+pub mod strategies;
 
 /// A wrapper for a fully functioning AI driven robot that cleans up garbage and extinguishes fire
 ///
@@ -20,25 +42,72 @@ mod runnable;
 pub struct WrapperTrashinatorRobot {
     runner: Runner,
     state: Rc<RefCell<AiState>>,
-    _world_generator: WorldgeneratorUnwrap
+    seed: u64,
+    recorder: Option<TickRecorder<BufWriter<File>>>,
+    known_map: Rc<RefCell<KnownMap>>,
+    _world_generator: Box<dyn Generator>
 }
 
 impl WrapperTrashinatorRobot {
     /// Creates a new `WrapperTrashinatorRobot` that will stop after completing `tasks_to_complete` tasks
-    pub fn new(tasks_to_complete: usize) -> WrapperTrashinatorRobot {
-        let mut world_generator = WorldgeneratorUnwrap::init(false, None);
+    ///
+    /// The `strategies` decide how the robot behaves in each stage of a process tick,
+    /// use `Strategies::default()` for the default behaviour
+    pub fn new(tasks_to_complete: usize, strategies: Strategies) -> WrapperTrashinatorRobot {
+        WrapperTrashinatorRobotBuilder::new(tasks_to_complete)
+            .strategies(strategies)
+            .build()
+            .unwrap()
+    }
+
+    /// Returns a builder to choose the world generator, a saved world or the generation parameters
+    pub fn builder(tasks_to_complete: usize) -> WrapperTrashinatorRobotBuilder {
+        WrapperTrashinatorRobotBuilder::new(tasks_to_complete)
+    }
+
+    /// Returns the seed of the RNG used by the robot, building a robot with the same seed on the same world reproduces the run
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
 
-        let state = Rc::new(RefCell::new(AiState::new()));
-        let runner = TrashinatorRobot::new(Robot::new(), state.clone(), tasks_to_complete);
-        let runner = Runner::new(Box::new(runner), &mut world_generator).unwrap();
+    /// Starts recording every following process tick in the file at `path`, it can be played back with a `TickReplayer`
+    pub fn start_recording(&mut self, path: impl AsRef<Path>) -> Result<(), RecordingError> {
+        self.recorder = Some(TickRecorder::create(path, self.seed)?);
+        Ok(())
+    }
 
-        WrapperTrashinatorRobot {
-            runner,
-            state,
-            _world_generator: world_generator
+    /// Stops recording, flushing what has been recorded so far
+    pub fn stop_recording(&mut self) -> Result<(), RecordingError> {
+        match self.recorder.take() {
+            None => Ok(()),
+            Some(mut recorder) => recorder.flush(),
         }
     }
 
+    /// Returns the number of process ticks performed
+    pub fn ticks(&self) -> usize {
+        self.state.borrow().tick
+    }
+
+    /// Returns every tile known by the robot, with its position, energy and backpack
+    ///
+    /// Allows a visualizer to sync in one call, e.g. when it joins late or missed some ticks
+    pub fn known_map_snapshot(&self) -> KnownMapSnapshot {
+        KnownMapSnapshot::full(&self.known_map.borrow(), &self.state.borrow())
+    }
+
+    /// Returns the tiles discovered or changed after the process tick `since_tick`, with the position, energy and backpack of the robot
+    ///
+    /// Pass the `tick` of the last snapshot or diff received to get only what changed after it
+    pub fn known_map_diff(&self, since_tick: usize) -> KnownMapSnapshot {
+        KnownMapSnapshot::diff(&self.known_map.borrow(), since_tick, &self.state.borrow())
+    }
+
+    /// Returns the state of the robot, updated at the end of each process tick
+    pub fn state(&self) -> Ref<'_, AiState> {
+        self.state.borrow()
+    }
+
     /// Performs a process tick
     ///
     /// Returns a tuple containing:
@@ -48,7 +117,9 @@ impl WrapperTrashinatorRobot {
     pub fn ai_process_tick(&mut self) -> (bool, Vec<Event>, Vec<(Tile, (usize, usize))>) {
         // Reset the state to prepare for the process tick
         self.state.borrow_mut().discovered_tiles = vec![];
+        self.state.borrow_mut().changed_tiles = vec![];
         self.state.borrow_mut().events_of_tick = vec![];
+        self.state.borrow_mut().ai_events_of_tick = vec![];
 
         // Execute the process tick
         let _ = self.runner.game_tick();
@@ -58,6 +129,15 @@ impl WrapperTrashinatorRobot {
         let events = self.state.borrow().events_of_tick.clone();
         let tiles = self.state.borrow().discovered_tiles.clone();
 
+        if let Some(recorder) = self.recorder.as_mut() {
+            let record = TickRecord::from_state(&self.state.borrow());
+
+            if let Err(e) = recorder.record(&record) {
+                error!("Failed recording tick {}, the recording is stopped: {}", record.tick, e);
+                self.recorder = None;
+            }
+        }
+
         return (terminated, events, tiles);
     }
 }
\ No newline at end of file
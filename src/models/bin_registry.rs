@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use crate::navigation::manhattan_distance;
+
+/// Remembers every discovered bin with its remaining capacity
+pub(crate) struct BinRegistry {
+    /// Remaining capacity of each bin by coordinates, in the form of row, col
+    bins: HashMap<(usize, usize), usize>,
+}
+
+impl BinRegistry {
+    pub(crate) fn new() -> BinRegistry {
+        BinRegistry {
+            bins: HashMap::new(),
+        }
+    }
+
+    /// Stores a bin seen at the given coordinates, the range of `Bin` holds the current amount and the maximum
+    pub(crate) fn record(&mut self, coordinates: (usize, usize), range: &Range<usize>) {
+        self.bins.insert(coordinates, range.end.saturating_sub(range.start));
+    }
+
+    /// Forgets the bin at the given coordinates, e.g. when it's not there anymore
+    pub(crate) fn remove(&mut self, coordinates: (usize, usize)) {
+        self.bins.remove(&coordinates);
+    }
+
+    /// Lowers the remaining capacity of a bin after putting `amount` in it
+    pub(crate) fn consume(&mut self, coordinates: (usize, usize), amount: usize) {
+        if let Some(capacity) = self.bins.get_mut(&coordinates) {
+            *capacity = capacity.saturating_sub(amount);
+        }
+    }
+
+    /// Sets the remaining capacity of a bin to zero, e.g. when it refused the garbage
+    pub(crate) fn mark_full(&mut self, coordinates: (usize, usize)) {
+        if let Some(capacity) = self.bins.get_mut(&coordinates) {
+            *capacity = 0;
+        }
+    }
+
+    /// Returns the coordinates of the bins that can still accept something with their remaining capacity, sorted
+    pub(crate) fn capacities(&self) -> Vec<((usize, usize), usize)> {
+        let mut bins: Vec<((usize, usize), usize)> = self
+            .bins
+            .iter()
+            .filter(|(_, capacity)| **capacity > 0)
+            .map(|(coordinates, capacity)| (*coordinates, *capacity))
+            .collect();
+        bins.sort();
+
+        bins
+    }
+
+    /// Returns the closest bin that can accept `amount`
+    ///
+    /// If no bin can accept the whole amount the closest one that can accept something is returned
+    pub(crate) fn closest_with_capacity(&self, from: (usize, usize), amount: usize) -> Option<(usize, usize)> {
+        let closest = |minimum_capacity: usize| {
+            self.bins
+                .iter()
+                .filter(|(_, capacity)| **capacity >= minimum_capacity)
+                .min_by_key(|(coordinates, _)| (manhattan_distance(from, **coordinates), **coordinates))
+                .map(|(coordinates, _)| *coordinates)
+        };
+
+        closest(amount.max(1)).or_else(|| closest(1))
+    }
+}
+
+impl Default for BinRegistry {
+    fn default() -> Self {
+        BinRegistry::new()
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use robotics_lib::world::tile::Tile;
+use crate::navigation::neighbours;
+
+/// Number of process ticks whose changes are kept to compute diffs, older diffs return the whole map
+pub(crate) const CHANGE_LOG_TICKS: usize = 1000;
+
+/// Stores every tile the robot has discovered so far
+///
+/// The tick in which each tile was last observed is stored as well, to know whether what's known about it is up to date.
+///
+/// The `revision` is bumped whenever the walkable layout of the map might have changed
+/// (a new tile is discovered or the type of a known tile changes), so that cached paths
+/// know when they need to be planned again
+///
+/// The tiles discovered or changed in the current tick are kept aside, so that they can be exposed to visualizers.
+/// The ones of the previous ticks are kept in a change log, only for the ticks with changes and for the last
+/// `CHANGE_LOG_TICKS` ticks, so that visualizers can ask for what changed since a tick
+pub(crate) struct KnownMap {
+    size: Option<usize>,
+    tiles: HashMap<(usize, usize), Tile>,
+    observed_at: HashMap<(usize, usize), usize>,
+    changed_in_tick: HashSet<(usize, usize)>,
+    change_log: BTreeMap<usize, Vec<(usize, usize)>>,
+    tick: usize,
+    revision: usize,
+}
+
+impl KnownMap {
+    pub(crate) fn new() -> KnownMap {
+        KnownMap {
+            size: None,
+            tiles: HashMap::new(),
+            observed_at: HashMap::new(),
+            changed_in_tick: HashSet::new(),
+            change_log: BTreeMap::new(),
+            tick: 0,
+            revision: 0,
+        }
+    }
+
+    /// Size of the world, if known
+    pub(crate) fn size(&self) -> Option<usize> {
+        self.size
+    }
+
+    pub(crate) fn set_size(&mut self, size: usize) {
+        self.size = Some(size);
+    }
+
+    /// Sets the current tick, tiles inserted from now on are considered observed in this tick
+    ///
+    /// The changes of the previous tick are moved to the change log, dropping the ones older than `CHANGE_LOG_TICKS`
+    pub(crate) fn set_tick(&mut self, tick: usize) {
+        if !self.changed_in_tick.is_empty() {
+            self.change_log.insert(self.tick, self.changed_in_tick.drain().collect());
+        }
+
+        self.tick = tick;
+        self.change_log = self.change_log.split_off(&self.first_logged_tick());
+    }
+
+    /// The current tick
+    pub(crate) fn tick(&self) -> usize {
+        self.tick
+    }
+
+    /// The oldest tick whose changes are still in the change log
+    fn first_logged_tick(&self) -> usize {
+        self.tick.saturating_sub(CHANGE_LOG_TICKS)
+    }
+
+    /// The tiles discovered or changed since the last call to `set_tick`
+    pub(crate) fn changed_in_tick(&self) -> impl Iterator<Item = (&Tile, (usize, usize))> + '_ {
+        self.changed_in_tick
+            .iter()
+            .filter_map(|coordinates| self.tiles.get(coordinates).map(|tile| (tile, *coordinates)))
+    }
+
+    /// The coordinates of the tiles discovered or changed after `since_tick`
+    ///
+    /// Returns `None` if the changes of some of those ticks were already dropped from the change log
+    pub(crate) fn changed_since(&self, since_tick: usize) -> Option<HashSet<(usize, usize)>> {
+        if since_tick + 1 < self.first_logged_tick() {
+            return None;
+        }
+
+        let mut changed: HashSet<(usize, usize)> = self
+            .change_log
+            .range(since_tick + 1..)
+            .flat_map(|(_, changes)| changes.iter().copied())
+            .collect();
+
+        if self.tick > since_tick {
+            changed.extend(self.changed_in_tick.iter().copied());
+        }
+
+        Some(changed)
+    }
+
+    /// Every known tile with its coordinates
+    pub(crate) fn tiles(&self) -> impl Iterator<Item = (&Tile, (usize, usize))> + '_ {
+        self.tiles.iter().map(|(coordinates, tile)| (tile, *coordinates))
+    }
+
+    /// The tick in which the tile at the given coordinates was last observed
+    pub(crate) fn last_observed(&self, coordinates: (usize, usize)) -> Option<usize> {
+        self.observed_at.get(&coordinates).copied()
+    }
+
+    pub(crate) fn revision(&self) -> usize {
+        self.revision
+    }
+
+    pub(crate) fn get(&self, coordinates: (usize, usize)) -> Option<&Tile> {
+        self.tiles.get(&coordinates)
+    }
+
+    /// Stores a discovered tile, bumping the revision if the tile was unknown or its type changed
+    pub(crate) fn insert(&mut self, tile: &Tile, coordinates: (usize, usize)) {
+        let (changed_layout, changed) = match self.tiles.get(&coordinates) {
+            None => (true, true),
+            Some(known) => (
+                known.tile_type != tile.tile_type,
+                known.tile_type != tile.tile_type
+                    || known.content != tile.content
+                    || known.elevation != tile.elevation,
+            ),
+        };
+
+        if changed_layout {
+            self.revision += 1;
+        }
+        if changed {
+            self.changed_in_tick.insert(coordinates);
+        }
+
+        self.tiles.insert(coordinates, tile.clone());
+        self.observed_at.insert(coordinates, self.tick);
+    }
+
+    /// Whether the coordinates are inside the world, always true if the size is still unknown
+    pub(crate) fn is_within_bounds(&self, coordinates: (usize, usize)) -> bool {
+        match self.size {
+            None => true,
+            Some(size) => coordinates.0 < size && coordinates.1 < size,
+        }
+    }
+
+    /// Whether the tile at the given coordinates has been discovered
+    pub(crate) fn is_known(&self, coordinates: (usize, usize)) -> bool {
+        self.tiles.contains_key(&coordinates)
+    }
+
+    /// Number of discovered tiles
+    pub(crate) fn known_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Whether the tile is a frontier: a known walkable tile adjacent to an unknown tile of the world
+    pub(crate) fn is_frontier(&self, coordinates: (usize, usize)) -> bool {
+        self.is_known(coordinates)
+            && self.is_walkable(coordinates)
+            && neighbours(coordinates)
+                .into_iter()
+                .any(|neighbour| self.is_within_bounds(neighbour) && !self.is_known(neighbour))
+    }
+
+    /// Whether the robot can walk on the tile at the given coordinates
+    ///
+    /// Unknown tiles are optimistically considered walkable, the path gets planned again once they are discovered
+    pub(crate) fn is_walkable(&self, coordinates: (usize, usize)) -> bool {
+        if !self.is_within_bounds(coordinates) {
+            return false;
+        }
+
+        match self.tiles.get(&coordinates) {
+            None => true,
+            Some(tile) => tile.tile_type.properties().walk(),
+        }
+    }
+}
+
+impl Default for KnownMap {
+    fn default() -> Self {
+        KnownMap::new()
+    }
+}
@@ -1 +1,3 @@
-pub(crate) mod task;
+pub(crate) mod bin_registry;
+pub(crate) mod known_map;
+pub mod task;
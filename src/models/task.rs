@@ -1,20 +1,102 @@
+use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use robotics_lib::utils::LibError;
+use serde::{Deserialize, Serialize};
+use crate::models::known_map::KnownMap;
+use crate::navigation::is_step;
+use crate::navigation::pathfinding::find_path;
 
 /// Stores the action and coordinates needed to execute the task
-#[derive(PartialEq, Eq, Hash, Debug)]
-pub(crate) struct Task {
-    pub(crate) action: TaskAction,
+///
+/// Two tasks are considered equal when they have the same action and coordinates, the cached path, the attempts and the creation tick are ignored
+#[derive(Debug)]
+pub struct Task {
+    pub action: TaskAction,
     /// Coordinates in the form of row, col
-    pub(crate) coordinates: (usize, usize),
+    pub coordinates: (usize, usize),
+    /// Cached path to a tile adjacent to the task coordinates, without the current robot position
+    pub(crate) path: Option<VecDeque<(usize, usize)>>,
+    /// Revision of the known map at the time the path was planned
+    pub(crate) path_revision: usize,
+    /// Position of the robot and revision of the known map of the last search that found no path
+    pub(crate) unreachable_from: Option<((usize, usize), usize)>,
+    /// Number of times the task failed
+    pub(crate) attempts: usize,
+    /// Process tick in which the task was created, only the observations of its target from then on can make it stale
+    pub(crate) created_tick: usize,
 }
 
 impl Task {
-    pub(crate) fn new(action: TaskAction, coordinates: (usize, usize)) -> Task {
+    pub fn new(action: TaskAction, coordinates: (usize, usize)) -> Task {
         Task {
             action,
             coordinates,
+            path: None,
+            path_revision: 0,
+            unreachable_from: None,
+            attempts: 0,
+            created_tick: 0,
         }
     }
+
+    /// Whether the cached path can still be followed from `current`
+    ///
+    /// The path is valid if it was planned on the given revision of the known map and its next step can be reached
+    /// from `current` with a single step, walking or through the `teleports`
+    pub(crate) fn has_valid_path(&self, teleports: &[(usize, usize)], current: (usize, usize), revision: usize) -> bool {
+        match &self.path {
+            None => false,
+            Some(path) => {
+                self.path_revision == revision
+                    && path.front().map_or(false, |next| is_step(teleports, current, *next))
+            }
+        }
+    }
+
+    /// Plans the path from `current` to a tile adjacent to the task and caches it, unless the cached one is still valid
+    ///
+    /// A search that found no path is cached as well, it's not repeated until the robot moves or the known map changes.
+    /// Returns whether a new search was performed
+    pub(crate) fn plan_path(
+        &mut self,
+        known_map: &KnownMap,
+        teleports: &[(usize, usize)],
+        current: (usize, usize),
+    ) -> bool {
+        let revision = known_map.revision();
+
+        if self.has_valid_path(teleports, current, revision) || self.unreachable_from == Some((current, revision)) {
+            return false;
+        }
+
+        self.path = find_path(known_map, teleports, current, self.coordinates);
+        self.path_revision = revision;
+        self.unreachable_from = if self.path.is_none() { Some((current, revision)) } else { None };
+
+        true
+    }
+
+    /// Drops the cached path so that it gets planned again
+    pub(crate) fn invalidate_path(&mut self) {
+        self.path = None;
+        self.unreachable_from = None;
+    }
+}
+
+impl PartialEq for Task {
+    fn eq(&self, other: &Self) -> bool {
+        self.action == other.action && self.coordinates == other.coordinates
+    }
+}
+
+impl Eq for Task {}
+
+impl Hash for Task {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.action.hash(state);
+        self.coordinates.hash(state);
+    }
 }
 
 impl Display for Task {
@@ -27,16 +109,36 @@ impl Display for Task {
     }
 }
 
+/// A copy of a task and its priority, used to expose the tasks to visualizers and recordings
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskSnapshot {
+    pub action: TaskAction,
+    /// Coordinates in the form of row, col
+    pub coordinates: (usize, usize),
+    /// Priority computed by the `TaskScorer`, comparable with the priorities of the pq
+    pub priority: usize,
+}
+
+impl TaskSnapshot {
+    pub(crate) fn new(task: &Task, priority: usize) -> TaskSnapshot {
+        TaskSnapshot {
+            action: task.action.clone(),
+            coordinates: task.coordinates,
+            priority,
+        }
+    }
+}
+
 /// Represents the action of a task stored in the priority queue
-#[derive(PartialEq, Eq, Hash, Debug)]
-pub(crate) enum TaskAction {
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
+pub enum TaskAction {
     DestroyFire,
     DestroyGarbage,
     PutGarbageInBin,
 }
 
 impl TaskAction {
-    pub(crate) fn get_priority_for_task(&self) -> usize {
+    pub fn get_priority_for_task(&self) -> usize {
         match self {
             TaskAction::DestroyFire => 100,
             TaskAction::DestroyGarbage => 50,
@@ -44,3 +146,46 @@ impl TaskAction {
         }
     }
 }
+
+/// Reason why a task failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskFailure {
+    /// No path to the task could be found
+    Unreachable,
+    /// The robot didn't have enough energy to move or perform the action
+    NotEnoughEnergy,
+    /// The backpack didn't have enough space for the collected content
+    NotEnoughSpace,
+    /// The robot couldn't walk on the next tile of the path
+    Blocked,
+    /// The action succeeded but nothing was collected
+    NothingCollected,
+    /// The target tile didn't have the expected content anymore
+    NoContent,
+    /// Any other error returned by `robotics_lib`
+    Other(String),
+}
+
+impl TaskFailure {
+    /// Whether the task might succeed if tried again later
+    pub fn is_transient(&self) -> bool {
+        !matches!(self, TaskFailure::NoContent | TaskFailure::Other(_))
+    }
+
+    /// Whether the failure counts towards the attempts of the task, lack of energy doesn't since it just needs time
+    pub fn uses_attempt(&self) -> bool {
+        *self != TaskFailure::NotEnoughEnergy
+    }
+}
+
+impl From<&LibError> for TaskFailure {
+    fn from(error: &LibError) -> Self {
+        match error {
+            LibError::NotEnoughEnergy => TaskFailure::NotEnoughEnergy,
+            LibError::NotEnoughSpace(_) => TaskFailure::NotEnoughSpace,
+            LibError::CannotWalk => TaskFailure::Blocked,
+            LibError::NoContent => TaskFailure::NoContent,
+            error => TaskFailure::Other(format!("{:?}", error)),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use robotics_lib::interface::Direction;
+
+/// Helpers to reason about `Direction`s as offsets on the world grid
+pub(crate) trait DirectionExt {
+    /// The offset in rows and columns of a step in the direction
+    fn delta(&self) -> (i64, i64);
+    /// The direction pointing the other way
+    fn opposite(&self) -> Direction;
+    /// The direction a quarter turn clockwise
+    fn rotate_clockwise(&self) -> Direction;
+    /// The direction a quarter turn counterclockwise
+    fn rotate_counterclockwise(&self) -> Direction;
+}
+
+impl DirectionExt for Direction {
+    fn delta(&self) -> (i64, i64) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    fn rotate_clockwise(&self) -> Direction {
+        self.rotate_counterclockwise().opposite()
+    }
+
+    fn rotate_counterclockwise(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+}
+
+/// All the directions, in a fixed order
+pub(crate) const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+/// Adds an offset in rows and columns to the coordinates, `None` if the result would be negative
+pub(crate) fn offset(coordinates: (usize, usize), delta: (i64, i64)) -> Option<(usize, usize)> {
+    let row = usize::try_from(coordinates.0 as i64 + delta.0).ok()?;
+    let col = usize::try_from(coordinates.1 as i64 + delta.1).ok()?;
+
+    Some((row, col))
+}
+
+/// The coordinates of the adjacent tile in the direction, `None` if they would be negative
+pub(crate) fn step(coordinates: (usize, usize), direction: &Direction) -> Option<(usize, usize)> {
+    offset(coordinates, direction.delta())
+}
+
+/// Keeps the coordinates only if they are inside a world of the given size, always if the size is unknown
+pub(crate) fn clip(coordinates: (usize, usize), size: Option<usize>) -> Option<(usize, usize)> {
+    match size {
+        Some(size) if coordinates.0 >= size || coordinates.1 >= size => None,
+        _ => Some(coordinates),
+    }
+}
+
+/// Maps a tile of the result of `one_direction_view` to world coordinates
+///
+/// Looking up or down the view is made of rows at increasing distance, each spanning the columns next to the robot,
+/// looking left or right it's made of the rows next to the robot, each spanning columns at increasing distance.
+/// `index` is the position of the tile in the view (outer, inner). The view is usually three tiles wide, centered on
+/// the robot, but it's narrower when clipped by the world edges: a view clipped on the top or left side starts at the
+/// edge, one clipped on the other side just ends earlier.
+///
+/// Returns `None` if the coordinates would be negative or outside a world of the given size
+pub(crate) fn one_direction_view_coordinates(
+    position: (usize, usize),
+    direction: &Direction,
+    index: (usize, usize),
+    size: Option<usize>,
+) -> Option<(usize, usize)> {
+    let (distance, side) = match direction {
+        Direction::Up | Direction::Down => (index.0, index.1),
+        Direction::Left | Direction::Right => (index.1, index.0),
+    };
+
+    // The coordinate across the direction, the view can't start before the edge of the world
+    let across = match direction {
+        Direction::Up | Direction::Down => position.1,
+        Direction::Left | Direction::Right => position.0,
+    };
+    let side_offset = side as i64 - across.min(1) as i64;
+
+    // The side index always grows with the row or the column, whichever is across the direction
+    let (forward_row, forward_col) = direction.delta();
+    let steps = distance as i64 + 1;
+    let delta = match direction {
+        Direction::Up | Direction::Down => (forward_row * steps, side_offset),
+        Direction::Left | Direction::Right => (side_offset, forward_col * steps),
+    };
+
+    offset(position, delta).and_then(|coordinates| clip(coordinates, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: Option<usize> = Some(5);
+
+    #[test]
+    fn direction_helpers() {
+        for direction in DIRECTIONS {
+            let (row, col) = direction.delta();
+            let (opposite_row, opposite_col) = direction.opposite().delta();
+
+            assert_eq!((row + opposite_row, col + opposite_col), (0, 0));
+            assert_eq!(direction.rotate_clockwise().rotate_counterclockwise(), direction);
+            assert_eq!(direction.rotate_clockwise().rotate_clockwise(), direction.opposite());
+        }
+
+        assert_eq!(Direction::Up.rotate_clockwise(), Direction::Right);
+        assert_eq!(Direction::Right.rotate_clockwise(), Direction::Down);
+    }
+
+    #[test]
+    fn step_at_the_edges() {
+        assert_eq!(step((0, 2), &Direction::Up), None);
+        assert_eq!(step((2, 0), &Direction::Left), None);
+        assert_eq!(step((4, 2), &Direction::Down).and_then(|c| clip(c, SIZE)), None);
+        assert_eq!(step((2, 4), &Direction::Right).and_then(|c| clip(c, SIZE)), None);
+
+        assert_eq!(step((0, 2), &Direction::Down), Some((1, 2)));
+        assert_eq!(step((2, 0), &Direction::Right), Some((2, 1)));
+        assert_eq!(step((4, 2), &Direction::Up), Some((3, 2)));
+        assert_eq!(step((2, 4), &Direction::Left), Some((2, 3)));
+    }
+
+    #[test]
+    fn offset_and_clip() {
+        assert_eq!(offset((0, 0), (-1, 0)), None);
+        assert_eq!(offset((0, 0), (0, -1)), None);
+        assert_eq!(offset((1, 1), (-1, -1)), Some((0, 0)));
+        assert_eq!(clip((5, 0), SIZE), None);
+        assert_eq!(clip((0, 5), SIZE), None);
+        assert_eq!(clip((4, 4), SIZE), Some((4, 4)));
+        assert_eq!(clip((100, 100), None), Some((100, 100)));
+    }
+
+    #[test]
+    fn one_direction_view_in_the_middle() {
+        let position = (2, 2);
+
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (0, 0), SIZE), Some((1, 1)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (1, 2), SIZE), Some((0, 3)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Down, (0, 1), SIZE), Some((3, 2)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (0, 0), SIZE), Some((1, 1)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (2, 1), SIZE), Some((3, 0)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Right, (1, 1), SIZE), Some((2, 4)));
+    }
+
+    #[test]
+    fn one_direction_view_at_the_top_edge() {
+        let position = (0, 2);
+
+        // Nothing is above the robot
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (0, 1), SIZE), None);
+        // The view to the left starts at the top row
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (0, 0), SIZE), Some((0, 1)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (1, 1), SIZE), Some((1, 0)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Right, (1, 0), SIZE), Some((1, 3)));
+    }
+
+    #[test]
+    fn one_direction_view_at_the_left_edge() {
+        let position = (2, 0);
+
+        // Nothing is left of the robot
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (1, 0), SIZE), None);
+        // The view upwards starts at the leftmost column
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (0, 0), SIZE), Some((1, 0)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (1, 1), SIZE), Some((0, 1)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Down, (0, 1), SIZE), Some((3, 1)));
+    }
+
+    #[test]
+    fn one_direction_view_at_the_bottom_edge() {
+        let position = (4, 2);
+
+        // Nothing is below the robot
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Down, (0, 1), SIZE), None);
+        // The view to the right ends at the bottom row
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Right, (0, 0), SIZE), Some((3, 3)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Right, (1, 0), SIZE), Some((4, 3)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Right, (2, 0), SIZE), None);
+    }
+
+    #[test]
+    fn one_direction_view_at_the_right_edge() {
+        let position = (2, 4);
+
+        // Nothing is right of the robot
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Right, (1, 0), SIZE), None);
+        // The view upwards ends at the rightmost column
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (0, 0), SIZE), Some((1, 3)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (0, 1), SIZE), Some((1, 4)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Up, (0, 2), SIZE), None);
+        // Looking across the whole world stops at the opposite edge
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (1, 3), SIZE), Some((2, 0)));
+        assert_eq!(one_direction_view_coordinates(position, &Direction::Left, (1, 4), SIZE), None);
+    }
+}
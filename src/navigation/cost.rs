@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+use crate::models::known_map::KnownMap;
+use crate::navigation::manhattan_distance;
+
+/// Energy cost assumed for walking on a tile that hasn't been discovered yet
+pub(crate) const UNKNOWN_TILE_COST: usize = 3;
+
+/// Energy consumed by `teleport`
+pub(crate) const TELEPORT_COST: usize = 30;
+
+/// Extra weight added to every step by the path planner
+///
+/// Keeps the heuristic admissible even on tiles that cost no energy, and makes the planner pick the
+/// shortest path among the ones that cost the same energy
+pub(crate) const STEP_PENALTY: usize = 1;
+
+/// Predicts the energy needed to walk from a tile to an adjacent one
+///
+/// Mirrors the cost of `go`: the walk cost of the destination tile type plus the squared elevation
+/// difference when climbing. Unknown tiles cost `UNKNOWN_TILE_COST` and no elevation difference.
+pub(crate) fn walk_cost(known_map: &KnownMap, from: (usize, usize), to: (usize, usize)) -> usize {
+    let to_tile = known_map.get(to);
+
+    let base_cost = to_tile.map_or(UNKNOWN_TILE_COST, |tile| tile.tile_type.properties().cost());
+
+    let elevation_cost = match (known_map.get(from), to_tile) {
+        (Some(from_tile), Some(to_tile)) if to_tile.elevation > from_tile.elevation => {
+            (to_tile.elevation - from_tile.elevation).pow(2)
+        }
+        _ => 0,
+    };
+
+    base_cost + elevation_cost
+}
+
+/// Predicts the energy needed for a step of a path: walking to an adjacent tile or teleporting to a far one
+pub(crate) fn step_cost(known_map: &KnownMap, from: (usize, usize), to: (usize, usize)) -> usize {
+    if manhattan_distance(from, to) == 1 {
+        walk_cost(known_map, from, to)
+    } else {
+        TELEPORT_COST
+    }
+}
+
+/// Predicts the energy needed to walk the given path starting from `start`, teleports included
+pub(crate) fn path_energy_cost(
+    known_map: &KnownMap,
+    start: (usize, usize),
+    path: &VecDeque<(usize, usize)>,
+) -> usize {
+    let mut previous = start;
+    let mut cost = 0;
+
+    for step in path.iter() {
+        cost += step_cost(known_map, previous, *step);
+        previous = *step;
+    }
+
+    cost
+}
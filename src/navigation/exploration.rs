@@ -0,0 +1,80 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use priority_queue::PriorityQueue;
+use crate::models::known_map::KnownMap;
+use crate::navigation::cost::{step_cost, STEP_PENALTY};
+use crate::navigation::{is_step, neighbours, teleport_hops};
+
+/// Finds the cheapest path from `start` to the nearest frontier tile
+///
+/// A frontier is a known walkable tile next to an unknown tile of the world, reaching it lets the robot
+/// discover new tiles. Differently from the task path planning only known tiles are walked on.
+/// Like in the task path planning the known `teleports` can be used to reach far away frontiers.
+///
+/// Returns the coordinates of each step, excluding `start`, or `None` if no frontier can be reached
+pub(crate) fn find_nearest_frontier(
+    known_map: &KnownMap,
+    teleports: &[(usize, usize)],
+    start: (usize, usize),
+) -> Option<VecDeque<(usize, usize)>> {
+    let mut open = PriorityQueue::new();
+    let mut visited = HashSet::new();
+    let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut costs: HashMap<(usize, usize), usize> = HashMap::new();
+
+    costs.insert(start, 0);
+    open.push(start, Reverse(0));
+
+    while let Some((current, Reverse(current_cost))) = open.pop() {
+        visited.insert(current);
+
+        if current != start && known_map.is_frontier(current) {
+            let mut path = VecDeque::new();
+            let mut step = current;
+
+            while let Some(previous) = came_from.get(&step) {
+                path.push_front(step);
+                step = *previous;
+            }
+
+            return Some(path);
+        }
+
+        for neighbour in neighbours(current).into_iter().chain(teleport_hops(teleports, current)) {
+            if visited.contains(&neighbour)
+                || !known_map.is_known(neighbour)
+                || !known_map.is_walkable(neighbour)
+            {
+                continue;
+            }
+
+            let cost = current_cost + step_cost(known_map, current, neighbour) + STEP_PENALTY;
+
+            if costs.get(&neighbour).map_or(true, |known_cost| cost < *known_cost) {
+                came_from.insert(neighbour, current);
+                costs.insert(neighbour, cost);
+                open.push_increase(neighbour, Reverse(cost));
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether a path towards a frontier can still be followed from `current`
+///
+/// The path is valid if its next step can be reached from `current` with a single step, all of its steps are
+/// still walkable and its last step is still a frontier
+pub(crate) fn is_exploration_path_valid(
+    known_map: &KnownMap,
+    teleports: &[(usize, usize)],
+    current: (usize, usize),
+    path: &VecDeque<(usize, usize)>,
+) -> bool {
+    let next_is_adjacent = path
+        .front()
+        .map_or(false, |next| is_step(teleports, current, *next));
+    let target_is_frontier = path.back().map_or(false, |target| known_map.is_frontier(*target));
+
+    next_is_adjacent && target_is_frontier && path.iter().all(|step| known_map.is_walkable(*step))
+}
@@ -0,0 +1,66 @@
+pub(crate) mod coordinates;
+pub(crate) mod cost;
+pub(crate) mod exploration;
+pub(crate) mod pathfinding;
+pub(crate) mod route;
+pub(crate) mod scan;
+
+use robotics_lib::interface::Direction;
+use crate::navigation::coordinates::{step, DIRECTIONS};
+
+/// Returns the coordinates of the 4 tiles adjacent to the given ones, skipping negative coordinates
+pub(crate) fn neighbours(coordinates: (usize, usize)) -> Vec<(usize, usize)> {
+    DIRECTIONS
+        .iter()
+        .filter_map(|direction| step(coordinates, direction))
+        .collect()
+}
+
+/// Returns the known teleports that can be reached from `from` with a single `teleport`, none if `from` isn't a teleport
+pub(crate) fn teleport_hops(
+    teleports: &[(usize, usize)],
+    from: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let is_teleport = teleports.contains(&from);
+
+    teleports
+        .iter()
+        .copied()
+        .filter(move |teleport| is_teleport && *teleport != from)
+}
+
+/// Whether the robot can get from `from` to `to` in a single step, by walking to an adjacent tile or by teleporting
+pub(crate) fn is_step(teleports: &[(usize, usize)], from: (usize, usize), to: (usize, usize)) -> bool {
+    manhattan_distance(from, to) == 1 || teleport_hops(teleports, from).any(|teleport| teleport == to)
+}
+
+/// How the robot reaches the next tile of a path
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Step {
+    /// Walk to the adjacent tile in the direction
+    Go(Direction),
+    /// Teleport to the teleport at the coordinates, in the form of row, col
+    Teleport((usize, usize)),
+}
+
+/// Returns how to reach `to` from `from` in a single step, `None` if it's not possible
+pub(crate) fn step_towards(teleports: &[(usize, usize)], from: (usize, usize), to: (usize, usize)) -> Option<Step> {
+    match direction_towards(from, to) {
+        Some(direction) => Some(Step::Go(direction)),
+        None if is_step(teleports, from, to) => Some(Step::Teleport(to)),
+        None => None,
+    }
+}
+
+/// Returns the `Direction` to go from `from` to `to` if the two coordinates are adjacent
+pub(crate) fn direction_towards(from: (usize, usize), to: (usize, usize)) -> Option<Direction> {
+    DIRECTIONS
+        .iter()
+        .find(|direction| step(from, direction) == Some(to))
+        .cloned()
+}
+
+/// Manhattan distance between two coordinates
+pub(crate) fn manhattan_distance(from: (usize, usize), to: (usize, usize)) -> usize {
+    from.0.abs_diff(to.0) + from.1.abs_diff(to.1)
+}
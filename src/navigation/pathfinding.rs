@@ -0,0 +1,279 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use priority_queue::PriorityQueue;
+use crate::models::known_map::KnownMap;
+use crate::navigation::cost::{step_cost, STEP_PENALTY, TELEPORT_COST};
+use crate::navigation::{manhattan_distance, neighbours, teleport_hops};
+
+/// Finds the cheapest path in terms of energy from `start` to a tile adjacent to `target` using A*
+///
+/// Each step weighs its predicted energy cost plus `STEP_PENALTY`, so the Manhattan distance is an
+/// admissible heuristic.
+///
+/// The known `teleports` are edges between each other costing `TELEPORT_COST`, the heuristic also
+/// considers going through the closest ones so that it stays admissible.
+///
+/// Only the tiles the robot has discovered are taken into account, unknown tiles inside the world are
+/// considered walkable so that the robot can plan through areas it has not seen yet.
+/// The target itself is never walked on, since tasks are performed from an adjacent tile.
+///
+/// The search isn't delegated to the `AStar_conRUSTulazioni` tool because it has to run on the `KnownMap`,
+/// which includes the assumptions made on the tiles that haven't been discovered yet.
+///
+/// Returns the coordinates of each step, excluding `start`, or `None` if the target can't be reached
+pub(crate) fn find_path(
+    known_map: &KnownMap,
+    teleports: &[(usize, usize)],
+    start: (usize, usize),
+    target: (usize, usize),
+) -> Option<VecDeque<(usize, usize)>> {
+    let mut open = PriorityQueue::new();
+    let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut costs: HashMap<(usize, usize), usize> = HashMap::new();
+
+    let teleport_to_target = teleports
+        .iter()
+        .map(|teleport| manhattan_distance(*teleport, target))
+        .min();
+    let heuristic = |from: (usize, usize)| -> usize {
+        let direct = manhattan_distance(from, target) * STEP_PENALTY;
+        let to_teleport = teleports.iter().map(|teleport| manhattan_distance(from, *teleport)).min();
+
+        match (to_teleport, teleport_to_target) {
+            (Some(to_teleport), Some(from_teleport)) => direct
+                .min((to_teleport + from_teleport + 1) * STEP_PENALTY + TELEPORT_COST),
+            _ => direct,
+        }
+    };
+
+    costs.insert(start, 0);
+    open.push(start, Reverse(heuristic(start)));
+
+    while let Some((current, _)) = open.pop() {
+        if manhattan_distance(current, target) == 1 {
+            return Some(reconstruct_path(&came_from, current));
+        }
+
+        let current_cost = costs[&current];
+
+        for neighbour in neighbours(current).into_iter().chain(teleport_hops(teleports, current)) {
+            if neighbour == target || !known_map.is_walkable(neighbour) {
+                continue;
+            }
+
+            let cost = current_cost + step_cost(known_map, current, neighbour) + STEP_PENALTY;
+
+            if costs.get(&neighbour).map_or(true, |known_cost| cost < *known_cost) {
+                came_from.insert(neighbour, current);
+                costs.insert(neighbour, cost);
+                open.push_increase(neighbour, Reverse(cost + heuristic(neighbour)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Walks back the `came_from` map to build the path that ends in `end`
+fn reconstruct_path(
+    came_from: &HashMap<(usize, usize), (usize, usize)>,
+    end: (usize, usize),
+) -> VecDeque<(usize, usize)> {
+    let mut path = VecDeque::new();
+    let mut current = end;
+
+    while let Some(previous) = came_from.get(&current) {
+        path.push_front(current);
+        current = *previous;
+    }
+
+    path
+}
+
+/// Computes the number of steps and the energy needed to reach a tile adjacent to each of the `targets`
+///
+/// Runs a single Dijkstra search from `start` with the same weights and teleports of `find_path`, targets are never walked on.
+/// The search stops once every target has been reached or after `max_expansions` tiles have been expanded,
+/// targets that weren't reached are missing from the result
+pub(crate) fn path_costs_to_targets(
+    known_map: &KnownMap,
+    teleports: &[(usize, usize)],
+    start: (usize, usize),
+    targets: &[(usize, usize)],
+    max_expansions: usize,
+) -> HashMap<(usize, usize), (usize, usize)> {
+    let target_set: HashSet<(usize, usize)> = targets.iter().copied().collect();
+    let mut remaining = target_set.clone();
+    let mut results = HashMap::new();
+
+    let mut open = PriorityQueue::new();
+    // For each tile: weight used by the search, steps and energy
+    let mut costs: HashMap<(usize, usize), (usize, usize, usize)> = HashMap::new();
+    let mut expansions = 0;
+
+    costs.insert(start, (0, 0, 0));
+    open.push(start, Reverse(0));
+
+    while let Some((current, _)) = open.pop() {
+        let (weight, steps, energy) = costs[&current];
+
+        for neighbour in neighbours(current) {
+            if remaining.remove(&neighbour) {
+                results.insert(neighbour, (steps, energy));
+            }
+        }
+
+        if remaining.is_empty() || expansions >= max_expansions {
+            break;
+        }
+        expansions += 1;
+
+        for neighbour in neighbours(current).into_iter().chain(teleport_hops(teleports, current)) {
+            if target_set.contains(&neighbour) || !known_map.is_walkable(neighbour) {
+                continue;
+            }
+
+            let step_energy = step_cost(known_map, current, neighbour);
+            let neighbour_weight = weight + step_energy + STEP_PENALTY;
+
+            if costs.get(&neighbour).map_or(true, |known| neighbour_weight < known.0) {
+                costs.insert(neighbour, (neighbour_weight, steps + 1, energy + step_energy));
+                open.push_increase(neighbour, Reverse(neighbour_weight));
+            }
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+    use crate::navigation::cost::path_energy_cost;
+    use crate::navigation::exploration::find_nearest_frontier;
+    use super::*;
+
+    fn tile(tile_type: TileType) -> Tile {
+        Tile {
+            tile_type,
+            content: Content::None,
+            elevation: 0,
+        }
+    }
+
+    /// A world of the given size whose first `known_rows` rows are known grass
+    fn grass_map(size: usize, known_rows: usize) -> KnownMap {
+        let mut known_map = KnownMap::new();
+        known_map.set_size(size);
+
+        for row in 0..known_rows {
+            for col in 0..size {
+                known_map.insert(&tile(TileType::Grass), (row, col));
+            }
+        }
+
+        known_map
+    }
+
+    #[test]
+    fn straight_path_stops_next_to_the_target() {
+        let known_map = grass_map(5, 5);
+
+        let path = find_path(&known_map, &[], (0, 0), (0, 3));
+
+        assert_eq!(path, Some(VecDeque::from([(0, 1), (0, 2)])));
+        assert_eq!(find_path(&known_map, &[], (0, 0), (0, 1)), Some(VecDeque::new()));
+    }
+
+    #[test]
+    fn path_goes_around_tiles_that_cant_be_walked() {
+        let mut known_map = grass_map(5, 5);
+        for row in 0..4 {
+            known_map.insert(&tile(TileType::DeepWater), (row, 1));
+        }
+
+        let path = find_path(&known_map, &[], (0, 0), (0, 3)).unwrap();
+
+        assert_eq!(path.len(), 10);
+        assert!(path.contains(&(4, 1)));
+        assert!(path.iter().all(|step| known_map.is_walkable(*step)));
+
+        // Closing the gap makes the target unreachable
+        known_map.insert(&tile(TileType::DeepWater), (4, 1));
+        assert_eq!(find_path(&known_map, &[], (0, 0), (0, 3)), None);
+    }
+
+    #[test]
+    fn path_goes_through_teleports_when_cheaper() {
+        let mut known_map = grass_map(20, 20);
+        let teleports = [(0, 1), (19, 18)];
+        for teleport in teleports {
+            known_map.insert(&tile(TileType::Teleport(true)), teleport);
+        }
+
+        let path = find_path(&known_map, &teleports, (0, 0), (19, 19));
+        assert_eq!(path, Some(VecDeque::from([(0, 1), (19, 18)])));
+
+        // Without knowing the teleports the robot walks
+        let path = find_path(&known_map, &[], (0, 0), (19, 19)).unwrap();
+        assert_eq!(path.len(), 37);
+    }
+
+    #[test]
+    fn path_costs_to_targets_with_a_single_search() {
+        let mut known_map = grass_map(10, 10);
+        // The last corner is walled off
+        known_map.insert(&tile(TileType::DeepWater), (8, 9));
+        known_map.insert(&tile(TileType::DeepWater), (9, 8));
+
+        let costs = path_costs_to_targets(&known_map, &[], (0, 0), &[(0, 3), (5, 5), (9, 9)], 1_000);
+
+        for target in [(0, 3), (5, 5)] {
+            let path = find_path(&known_map, &[], (0, 0), target).unwrap();
+            assert_eq!(costs.get(&target), Some(&(path.len(), path_energy_cost(&known_map, (0, 0), &path))));
+        }
+        assert!(!costs.contains_key(&(9, 9)));
+
+        // The search stops after the given number of expansions
+        let costs = path_costs_to_targets(&known_map, &[], (0, 0), &[(0, 1), (0, 3)], 0);
+        assert_eq!(costs, HashMap::from([((0, 1), (0, 0))]));
+    }
+
+    #[test]
+    fn nearest_frontier() {
+        let known_map = grass_map(10, 3);
+
+        // The last known row is next to the unknown tiles
+        let path = find_nearest_frontier(&known_map, &[], (0, 0));
+        assert_eq!(path, Some(VecDeque::from([(1, 0), (2, 0)])));
+
+        // A fully known world has no frontier
+        let known_map = grass_map(10, 10);
+        assert_eq!(find_nearest_frontier(&known_map, &[], (0, 0)), None);
+    }
+
+    #[test]
+    fn nearest_frontier_through_teleports() {
+        // The robot is in the first three columns, walled off by deep water from the last row, next to the unknown tiles
+        let mut known_map = grass_map(20, 0);
+        for row in 0..20 {
+            for col in 0..3 {
+                known_map.insert(&tile(TileType::Grass), (row, col));
+            }
+            known_map.insert(&tile(TileType::DeepWater), (row, 3));
+        }
+        for col in 4..20 {
+            known_map.insert(&tile(TileType::Grass), (19, col));
+        }
+
+        assert_eq!(find_nearest_frontier(&known_map, &[], (0, 0)), None);
+
+        let teleports = [(0, 1), (19, 10)];
+        for teleport in teleports {
+            known_map.insert(&tile(TileType::Teleport(true)), teleport);
+        }
+
+        let path = find_nearest_frontier(&known_map, &teleports, (0, 0));
+        assert_eq!(path, Some(VecDeque::from([(0, 1), (19, 10)])));
+    }
+}
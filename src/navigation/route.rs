@@ -0,0 +1,210 @@
+use crate::navigation::manhattan_distance;
+
+/// A stop of a route planned through several tasks
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RouteStop {
+    /// Perform the task at the coordinates
+    Task((usize, usize)),
+    /// Empty the backpack in the bin at the coordinates
+    Bin((usize, usize)),
+}
+
+/// Maximum number of passes of the 2-opt improvement
+const MAX_TWO_OPT_PASSES: usize = 50;
+
+/// Orders the `stops` in a short route starting from `start`
+///
+/// Builds the route with the nearest neighbour heuristic and improves it with 2-opt, distances are Manhattan distances.
+/// The route doesn't go back to `start`
+pub(crate) fn order_stops(start: (usize, usize), stops: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut remaining = stops.to_vec();
+    let mut route = Vec::with_capacity(stops.len());
+    let mut position = start;
+
+    while !remaining.is_empty() {
+        let (nearest, _) = remaining
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, stop)| (manhattan_distance(position, **stop), **stop))
+            .unwrap();
+
+        position = remaining.swap_remove(nearest);
+        route.push(position);
+    }
+
+    two_opt(start, &mut route);
+
+    route
+}
+
+/// Reverses segments of the route as long as doing so makes it shorter
+fn two_opt(start: (usize, usize), route: &mut [(usize, usize)]) {
+    for _ in 0..MAX_TWO_OPT_PASSES {
+        let mut improved = false;
+
+        for i in 0..route.len() {
+            for j in i + 1..route.len() {
+                let previous = if i == 0 { start } else { route[i - 1] };
+                let next = route.get(j + 1).copied();
+
+                let current_length = manhattan_distance(previous, route[i])
+                    + next.map_or(0, |next| manhattan_distance(route[j], next));
+                let reversed_length = manhattan_distance(previous, route[j])
+                    + next.map_or(0, |next| manhattan_distance(route[i], next));
+
+                if reversed_length < current_length {
+                    route[i..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Adds a visit to the nearest bin before each stop whose load doesn't fit in the backpack anymore
+///
+/// Only the bins with enough capacity left for everything carried at that point are visited,
+/// the capacity used by the earlier visits of the route is taken into account
+///
+/// - `loads`: the amount collected at each stop of the route
+/// - `free_space`: the space currently free in the backpack
+/// - `capacity`: the space free in the backpack after emptying it in a bin
+/// - `bins`: the coordinates of the known bins with their remaining capacity, if none can take the load the route is left as it is
+pub(crate) fn insert_bin_visits(
+    start: (usize, usize),
+    route: &[(usize, usize)],
+    loads: &[usize],
+    free_space: usize,
+    capacity: usize,
+    bins: &[((usize, usize), usize)],
+) -> Vec<RouteStop> {
+    let mut stops = Vec::with_capacity(route.len());
+    let mut bins = bins.to_vec();
+    let mut position = start;
+    let mut free_space = free_space;
+
+    for (stop, load) in route.iter().zip(loads.iter()) {
+        let carried = capacity.saturating_sub(free_space);
+
+        if *load > free_space {
+            let nearest_bin = bins
+                .iter_mut()
+                .filter(|(_, bin_capacity)| *bin_capacity >= carried)
+                .min_by_key(|(bin, _)| (manhattan_distance(position, *bin), *bin));
+
+            if let Some((bin, bin_capacity)) = nearest_bin {
+                *bin_capacity -= carried;
+                stops.push(RouteStop::Bin(*bin));
+                position = *bin;
+                free_space = capacity;
+            }
+        }
+
+        stops.push(RouteStop::Task(*stop));
+        position = *stop;
+        free_space = free_space.saturating_sub(*load);
+    }
+
+    stops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route_length(start: (usize, usize), route: &[(usize, usize)]) -> usize {
+        let mut previous = start;
+
+        route
+            .iter()
+            .map(|stop| {
+                let distance = manhattan_distance(previous, *stop);
+                previous = *stop;
+                distance
+            })
+            .sum()
+    }
+
+    #[test]
+    fn two_opt_improves_the_nearest_neighbour_route() {
+        let start = (0, 5);
+
+        // The nearest neighbour goes to (0, 4), then (0, 7) and back to (0, 0): 11 steps
+        let route = order_stops(start, &[(0, 4), (0, 7), (0, 0)]);
+
+        assert_eq!(route, vec![(0, 7), (0, 4), (0, 0)]);
+        assert_eq!(route_length(start, &route), 9);
+    }
+
+    #[test]
+    fn two_opt_untangles_a_route() {
+        let start = (0, 0);
+        let mut route = vec![(0, 4), (0, 1), (0, 3), (0, 2)];
+
+        two_opt(start, &mut route);
+
+        assert_eq!(route, vec![(0, 1), (0, 2), (0, 3), (0, 4)]);
+    }
+
+    #[test]
+    fn order_stops_visits_every_stop_once() {
+        let stops = [(3, 3), (0, 1), (7, 2), (0, 6), (5, 5), (2, 0)];
+
+        let mut route = order_stops((4, 4), &stops);
+        route.sort();
+        let mut expected = stops.to_vec();
+        expected.sort();
+
+        assert_eq!(route, expected);
+        assert!(order_stops((4, 4), &[]).is_empty());
+    }
+
+    #[test]
+    fn bins_are_visited_before_the_backpack_overflows() {
+        let route = [(0, 1), (0, 2), (0, 3)];
+        let loads = [2, 2, 2];
+
+        let stops = insert_bin_visits((0, 0), &route, &loads, 3, 10, &[((5, 5), 10), ((1, 1), 10)]);
+        assert_eq!(
+            stops,
+            vec![
+                RouteStop::Task((0, 1)),
+                RouteStop::Bin((1, 1)),
+                RouteStop::Task((0, 2)),
+                RouteStop::Task((0, 3)),
+            ]
+        );
+
+        // Without bins the route is left as it is
+        let stops = insert_bin_visits((0, 0), &route, &loads, 3, 10, &[]);
+        assert_eq!(stops, route.iter().map(|stop| RouteStop::Task(*stop)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn bins_without_capacity_for_the_load_are_skipped() {
+        let route = [(0, 1), (0, 2), (0, 3), (0, 4)];
+        let loads = [2, 2, 5, 5];
+
+        // The closest bin can't take the 7 carried, the second visit can't use the bin filled by the first one
+        let bins = [((0, 2), 5), ((4, 0), 9)];
+        let stops = insert_bin_visits((0, 0), &route, &loads, 2, 9, &bins);
+        assert_eq!(
+            stops,
+            vec![
+                RouteStop::Task((0, 1)),
+                RouteStop::Bin((4, 0)),
+                RouteStop::Task((0, 2)),
+                RouteStop::Task((0, 3)),
+                RouteStop::Task((0, 4)),
+            ]
+        );
+
+        // No bin can take the load, the route is left as it is
+        let stops = insert_bin_visits((0, 0), &route, &loads, 2, 9, &[((0, 2), 5)]);
+        assert_eq!(stops, route.iter().map(|stop| RouteStop::Task(*stop)).collect::<Vec<_>>());
+    }
+}
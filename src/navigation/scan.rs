@@ -0,0 +1,43 @@
+use robotics_lib::interface::Direction;
+use crate::navigation::coordinates::{offset, DirectionExt};
+use crate::strategies::detection::Scan;
+
+/// Returns the coordinates of the tiles covered by a scan from `position`, skipping negative coordinates
+pub(crate) fn scan_coverage(position: (usize, usize), scan: &Scan) -> Vec<(usize, usize)> {
+    let offsets: Vec<(i64, i64)> = match scan {
+        Scan::Around => (-1..=1)
+            .flat_map(|row| (-1..=1).map(move |col| (row, col)))
+            .collect(),
+        Scan::OneDirection(direction, distance) => {
+            let forward = direction.delta();
+            let across = direction.rotate_clockwise().delta();
+
+            (1..=*distance as i64)
+                .flat_map(|step| {
+                    (-1..=1).map(move |side| {
+                        (forward.0 * step + across.0 * side, forward.1 * step + across.1 * side)
+                    })
+                })
+                .collect()
+        }
+    };
+
+    offsets
+        .into_iter()
+        .filter_map(|delta| offset(position, delta))
+        .collect()
+}
+
+/// Returns the direction in which `target` mostly lies from `position`, `None` if they are the same
+pub(crate) fn heading_towards(position: (usize, usize), target: (usize, usize)) -> Option<Direction> {
+    let row_diff = target.0 as i64 - position.0 as i64;
+    let col_diff = target.1 as i64 - position.1 as i64;
+
+    if row_diff == 0 && col_diff == 0 {
+        None
+    } else if row_diff.abs() >= col_diff.abs() {
+        Some(if row_diff < 0 { Direction::Up } else { Direction::Down })
+    } else {
+        Some(if col_diff < 0 { Direction::Left } else { Direction::Right })
+    }
+}
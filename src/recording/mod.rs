@@ -0,0 +1,250 @@
+pub mod recorder;
+pub mod replayer;
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use robotics_lib::event::events::Event;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::{Content, Tile};
+use serde::{Deserialize, Serialize};
+use crate::models::task::TaskSnapshot;
+use crate::state::AiState;
+
+/// Version of the recording format, bumped whenever `RecordingHeader` or `TickRecord` change
+pub const RECORDING_VERSION: u32 = 1;
+
+/// Written once at the start of a recording
+///
+/// Properties:
+/// - version: the `RECORDING_VERSION` used to write the recording
+/// - seed: the seed of the recorded robot, allows reproducing the run on the same world
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub version: u32,
+    pub seed: u64,
+}
+
+/// Everything that happened in a single process tick
+///
+/// Properties:
+/// - tick: the number of the process tick, starting from 1
+/// - terminated: whether the robot had terminated at the end of the tick
+/// - robot_coordinates: the coordinates of the robot at the end of the tick, in the form of row, col
+/// - energy: the energy level of the robot at the end of the tick
+/// - backpack: the contents of the backpack at the end of the tick
+/// - current_task: the task the robot was performing
+/// - pending_tasks: the tasks in the priority queue, sorted by descending priority
+/// - events: the events occurred in the tick
+/// - discovered_tiles: the tiles discovered in the tick with their coordinates
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TickRecord {
+    pub tick: usize,
+    pub terminated: bool,
+    pub robot_coordinates: (usize, usize),
+    pub energy: usize,
+    pub backpack: HashMap<Content, usize>,
+    pub current_task: Option<TaskSnapshot>,
+    pub pending_tasks: Vec<TaskSnapshot>,
+    pub events: Vec<RecordedEvent>,
+    pub discovered_tiles: Vec<(Tile, (usize, usize))>,
+}
+
+impl TickRecord {
+    /// Creates the record of a tick from the state at the end of the tick
+    pub(crate) fn from_state(state: &AiState) -> TickRecord {
+        TickRecord {
+            tick: state.tick,
+            terminated: state.terminate,
+            robot_coordinates: state.robot_coordinates,
+            energy: state.energy,
+            backpack: state.backpack.clone(),
+            current_task: state.current_task.clone(),
+            pending_tasks: state.pending_tasks.clone(),
+            events: state.events_of_tick.iter().map(RecordedEvent::from).collect(),
+            discovered_tiles: state.discovered_tiles.clone(),
+        }
+    }
+
+    /// Returns the tick in the same form as `WrapperTrashinatorRobot::ai_process_tick`
+    pub fn to_process_tick_result(&self) -> (bool, Vec<Event>, Vec<(Tile, (usize, usize))>) {
+        let events = self.events.iter().cloned().map(Event::from).collect();
+
+        (self.terminated, events, self.discovered_tiles.clone())
+    }
+}
+
+/// Serializable copy of a `robotics_lib` `Event`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    Ready,
+    Terminated,
+    TimeChanged(EnvironmentalConditions),
+    DayChanged(EnvironmentalConditions),
+    EnergyRecharged(usize),
+    EnergyConsumed(usize),
+    Moved(Tile, (usize, usize)),
+    TileContentUpdated(Tile, (usize, usize)),
+    AddedToBackpack(Content, usize),
+    RemovedFromBackpack(Content, usize),
+}
+
+impl From<&Event> for RecordedEvent {
+    fn from(event: &Event) -> Self {
+        match event.clone() {
+            Event::Ready => RecordedEvent::Ready,
+            Event::Terminated => RecordedEvent::Terminated,
+            Event::TimeChanged(conditions) => RecordedEvent::TimeChanged(conditions),
+            Event::DayChanged(conditions) => RecordedEvent::DayChanged(conditions),
+            Event::EnergyRecharged(amount) => RecordedEvent::EnergyRecharged(amount),
+            Event::EnergyConsumed(amount) => RecordedEvent::EnergyConsumed(amount),
+            Event::Moved(tile, coordinates) => RecordedEvent::Moved(tile, coordinates),
+            Event::TileContentUpdated(tile, coordinates) => RecordedEvent::TileContentUpdated(tile, coordinates),
+            Event::AddedToBackpack(content, amount) => RecordedEvent::AddedToBackpack(content, amount),
+            Event::RemovedFromBackpack(content, amount) => RecordedEvent::RemovedFromBackpack(content, amount),
+        }
+    }
+}
+
+impl From<RecordedEvent> for Event {
+    fn from(event: RecordedEvent) -> Self {
+        match event {
+            RecordedEvent::Ready => Event::Ready,
+            RecordedEvent::Terminated => Event::Terminated,
+            RecordedEvent::TimeChanged(conditions) => Event::TimeChanged(conditions),
+            RecordedEvent::DayChanged(conditions) => Event::DayChanged(conditions),
+            RecordedEvent::EnergyRecharged(amount) => Event::EnergyRecharged(amount),
+            RecordedEvent::EnergyConsumed(amount) => Event::EnergyConsumed(amount),
+            RecordedEvent::Moved(tile, coordinates) => Event::Moved(tile, coordinates),
+            RecordedEvent::TileContentUpdated(tile, coordinates) => Event::TileContentUpdated(tile, coordinates),
+            RecordedEvent::AddedToBackpack(content, amount) => Event::AddedToBackpack(content, amount),
+            RecordedEvent::RemovedFromBackpack(content, amount) => Event::RemovedFromBackpack(content, amount),
+        }
+    }
+}
+
+/// Error occurred while writing or reading a recording
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(std::io::Error),
+    Encoding(bincode::Error),
+    UnsupportedVersion(u32),
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordingError::Io(e) => write!(f, "I/O error: {}", e),
+            RecordingError::Encoding(e) => write!(f, "encoding error: {}", e),
+            RecordingError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported recording version {}, expected {}",
+                version, RECORDING_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<std::io::Error> for RecordingError {
+    fn from(e: std::io::Error) -> Self {
+        RecordingError::Io(e)
+    }
+}
+
+impl From<bincode::Error> for RecordingError {
+    fn from(e: bincode::Error) -> Self {
+        RecordingError::Encoding(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use robotics_lib::world::tile::TileType;
+    use crate::models::task::TaskAction;
+    use crate::recording::recorder::TickRecorder;
+    use crate::recording::replayer::TickReplayer;
+    use super::*;
+
+    fn record(tick: usize) -> TickRecord {
+        let tile = Tile {
+            tile_type: TileType::Grass,
+            content: Content::Fire,
+            elevation: 2,
+        };
+
+        TickRecord {
+            tick,
+            terminated: false,
+            robot_coordinates: (tick, 3),
+            energy: 900 - tick,
+            backpack: HashMap::from([(Content::Garbage(0), tick)]),
+            current_task: Some(TaskSnapshot {
+                action: TaskAction::DestroyFire,
+                coordinates: (1, 2),
+                priority: 9_990,
+            }),
+            pending_tasks: vec![TaskSnapshot {
+                action: TaskAction::DestroyGarbage,
+                coordinates: (4, 4),
+                priority: 4_980,
+            }],
+            events: vec![RecordedEvent::EnergyConsumed(3), RecordedEvent::Moved(tile.clone(), (tick, 3))],
+            discovered_tiles: vec![(tile, (1, 2))],
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut bytes = vec![];
+        let mut recorder = TickRecorder::new(&mut bytes, 42).unwrap();
+        recorder.record(&record(0)).unwrap();
+        recorder.record(&record(1)).unwrap();
+        recorder.flush().unwrap();
+
+        let mut replayer = TickReplayer::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(replayer.seed(), 42);
+        assert_eq!(replayer.next_record().unwrap(), Some(record(0)));
+        assert_eq!(replayer.last_record(), Some(&record(0)));
+
+        let (terminated, events, tiles) = replayer.ai_process_tick();
+        assert!(!terminated);
+        assert_eq!(events.len(), 2);
+        assert_eq!(tiles, record(1).discovered_tiles);
+        assert_eq!(replayer.last_record(), Some(&record(1)));
+
+        // The recording is over
+        assert_eq!(replayer.next_record().unwrap(), None);
+        let (terminated, events, tiles) = replayer.ai_process_tick();
+        assert!(terminated && events.is_empty() && tiles.is_empty());
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let header = RecordingHeader {
+            version: RECORDING_VERSION + 1,
+            seed: 0,
+        };
+        let bytes = bincode::serialize(&header).unwrap();
+
+        assert!(matches!(
+            TickReplayer::new(Cursor::new(bytes)),
+            Err(RecordingError::UnsupportedVersion(version)) if version == RECORDING_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn events_round_trip() {
+        let events = [
+            RecordedEvent::Ready,
+            RecordedEvent::EnergyRecharged(10),
+            RecordedEvent::AddedToBackpack(Content::Garbage(0), 2),
+            RecordedEvent::RemovedFromBackpack(Content::Garbage(0), 1),
+        ];
+
+        for event in events {
+            assert_eq!(RecordedEvent::from(&Event::from(event.clone())), event);
+        }
+    }
+}
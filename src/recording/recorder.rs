@@ -0,0 +1,45 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::recording::{RecordingError, RecordingHeader, TickRecord, RECORDING_VERSION};
+
+/// Writes the ticks of a run in a compact binary format that can be read by a `TickReplayer`
+///
+/// The recording starts with a `RecordingHeader` followed by one `TickRecord` per tick
+pub struct TickRecorder<W: Write> {
+    writer: W,
+}
+
+impl TickRecorder<BufWriter<File>> {
+    /// Creates a recorder that writes to the file at `path`, overwriting it if it exists
+    pub fn create(path: impl AsRef<Path>, seed: u64) -> Result<Self, RecordingError> {
+        let file = File::create(path)?;
+
+        TickRecorder::new(BufWriter::new(file), seed)
+    }
+}
+
+impl<W: Write> TickRecorder<W> {
+    /// Creates a recorder that writes to `writer`, the header is written immediately
+    pub fn new(mut writer: W, seed: u64) -> Result<Self, RecordingError> {
+        let header = RecordingHeader {
+            version: RECORDING_VERSION,
+            seed,
+        };
+        bincode::serialize_into(&mut writer, &header)?;
+
+        Ok(TickRecorder { writer })
+    }
+
+    /// Appends a tick to the recording
+    pub fn record(&mut self, record: &TickRecord) -> Result<(), RecordingError> {
+        bincode::serialize_into(&mut self.writer, record)?;
+        Ok(())
+    }
+
+    /// Flushes the underlying writer
+    pub fn flush(&mut self) -> Result<(), RecordingError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+use log::error;
+use robotics_lib::event::events::Event;
+use robotics_lib::world::tile::Tile;
+use crate::recording::{RecordingError, RecordingHeader, TickRecord, RECORDING_VERSION};
+
+/// Reads the ticks written by a `TickRecorder`
+///
+/// Allows visualizers to play a run back through the same interface of `WrapperTrashinatorRobot::ai_process_tick`
+/// without running the simulation
+pub struct TickReplayer<R: Read> {
+    reader: R,
+    header: RecordingHeader,
+    last_record: Option<TickRecord>,
+}
+
+impl TickReplayer<BufReader<File>> {
+    /// Opens the recording at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        let file = File::open(path)?;
+
+        TickReplayer::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> TickReplayer<R> {
+    /// Creates a replayer that reads from `reader`, the header is read immediately
+    pub fn new(mut reader: R) -> Result<Self, RecordingError> {
+        let header: RecordingHeader = bincode::deserialize_from(&mut reader)?;
+
+        if header.version != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion(header.version));
+        }
+
+        Ok(TickReplayer {
+            reader,
+            header,
+            last_record: None,
+        })
+    }
+
+    /// Returns the seed of the recorded robot
+    pub fn seed(&self) -> u64 {
+        self.header.seed
+    }
+
+    /// Returns the last tick read, with the robot position, energy, backpack and tasks
+    pub fn last_record(&self) -> Option<&TickRecord> {
+        self.last_record.as_ref()
+    }
+
+    /// Reads the next tick, returns `None` when the recording is over
+    pub fn next_record(&mut self) -> Result<Option<TickRecord>, RecordingError> {
+        match bincode::deserialize_from::<_, TickRecord>(&mut self.reader) {
+            Ok(record) => {
+                self.last_record = Some(record.clone());
+                Ok(Some(record))
+            }
+            Err(e) => match e.as_ref() {
+                bincode::ErrorKind::Io(io_error) if io_error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+                _ => Err(e.into()),
+            },
+        }
+    }
+
+    /// Replays the next tick
+    ///
+    /// Returns the same tuple of `WrapperTrashinatorRobot::ai_process_tick`, the replay is considered
+    /// terminated when the recording is over or can't be read anymore
+    pub fn ai_process_tick(&mut self) -> (bool, Vec<Event>, Vec<(Tile, (usize, usize))>) {
+        match self.next_record() {
+            Ok(Some(record)) => record.to_process_tick_result(),
+            Ok(None) => (true, vec![], vec![]),
+            Err(e) => {
+                error!("Failed reading recorded tick: {}", e);
+                (true, vec![], vec![])
+            }
+        }
+    }
+}
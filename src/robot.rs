@@ -1,22 +1,55 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
 use std::rc::Rc;
-use bob_lib::enhanced_map::{bob_view, BobMap};
-use crate::models::task::{Task, TaskAction};
-use crate::state::AiState;
+use crate::energy::EnergyPolicy;
+use crate::error::{AiError, AiStage, MAX_LAST_ERRORS};
+use crate::goal::{Goal, GoalProgress};
+use crate::interface::{RobotStatus, WorldInterface};
+use crate::models::bin_registry::BinRegistry;
+use crate::models::known_map::KnownMap;
+use crate::models::task::{Task, TaskAction, TaskFailure, TaskSnapshot};
+use crate::navigation::coordinates::{clip, offset, one_direction_view_coordinates, DirectionExt, DIRECTIONS};
+use crate::navigation::cost::{path_energy_cost, UNKNOWN_TILE_COST};
+use crate::navigation::exploration::{find_nearest_frontier, is_exploration_path_valid};
+use crate::navigation::route::{insert_bin_visits, order_stops, RouteStop};
+use crate::navigation::scan::{heading_towards, scan_coverage};
+use crate::navigation::{direction_towards, manhattan_distance, pathfinding, step_towards, Step};
+use crate::state::{AiEvent, AiState};
+use crate::strategies::detection::{Scan, ScanConfig};
+use crate::strategies::scheduling::PreemptionConfig;
+use crate::strategies::scoring::{DefaultTaskScorer, TaskScoreInput, TaskScorer};
+use crate::strategies::Strategies;
 use charting_tools::charted_coordinate::ChartedCoordinate;
 use charting_tools::charted_map::ChartedMap;
 use charting_tools::ChartingTools;
 use log::{debug, error, info};
 use priority_queue::PriorityQueue;
-use rand::Rng;
-use robotics_lib::interface::{
-    destroy, go, one_direction_view, put, teleport, Direction,
-};
-use robotics_lib::runner::{Robot, Runnable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use robotics_lib::interface::Direction;
+use robotics_lib::runner::Robot;
+use robotics_lib::utils::LibError;
 use robotics_lib::world::tile::Content::{Bin, Fire, Garbage};
 use robotics_lib::world::tile::{Tile, TileType};
-use robotics_lib::world::World;
+
+/// Maximum number of tiles expanded by the search used to score the tasks in the pq
+const RESCORE_MAX_EXPANSIONS: usize = 20_000;
+
+/// Default amount of garbage in the backpack above which the robot goes to empty it in a bin
+pub(crate) const DEFAULT_DISPOSAL_THRESHOLD: usize = 5;
+
+/// Default number of times a task can fail for a transient reason before being dropped
+pub(crate) const DEFAULT_MAX_TASK_ATTEMPTS: usize = 3;
+
+/// What the robot does in a process tick to get closer to the completion of the current task
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum NextMove {
+    /// Perform the action of the task on the adjacent tile in the direction
+    Perform(Direction),
+    /// Take the next step of the path towards the task
+    Step(Step),
+}
 
 /// A fully functioning AI driven robot that cleans up garbage and extinguishes fire
 /// Properties:
@@ -24,130 +57,287 @@ use robotics_lib::world::World;
 /// - state: the state for storing useful data for a visualizer
 /// - pq: priority queue that stores tasks to execute
 /// - current_task: the current task that must be completed
-/// - marked_coords: the coordinates that have already been analyzed for tasks
+/// - route: the order in which the garbage tasks in the pq are performed, with the bin visits needed in between
+/// - tasks_pushed_in_tick: action and coordinates of the tasks added to the pq in the current process tick
+/// - marked_coords: the coordinates of the tasks that haven't been resolved yet
 /// - charted_map: tool used to store location of teleporters
+/// - known_map: all the tiles discovered by the robot, used for path planning
+/// - bins: every discovered bin with its remaining capacity
+/// - blacklist: coordinates that proved unreachable, no task is created for them
+/// - max_task_attempts: how many times a task can fail for a transient reason before being dropped
+/// - disposal_threshold: amount of garbage in the backpack above which the robot goes to empty it in a bin
+/// - energy_policy: the energy reserve and the estimated costs of the actions, used to decide when to rest
+/// - resting: whether the robot is resting to regenerate energy
+/// - exploration_path: path towards the nearest frontier, followed when there are no tasks to perform
+/// - exploration_complete: whether the robot ran out of reachable frontiers at least once
 /// - previous_move_direction: direction to which the robot moved in the last process tick
-/// - previous_one_directional_view_direction: direction in which the robot looked using the one directional view in the last process tick
+/// - ticks: the number of process ticks performed
 /// - tasks_completed
-/// - tasks_to_complete: set externally by users
+/// - garbage_collected: amount of garbage collected so far
+/// - garbage_deposited: amount of garbage put in bins so far
+/// - fires_extinguished: number of fires destroyed so far
+/// - goal: when the robot terminates, set externally by users
+/// - strategies: the strategies used for each stage of the process tick
+/// - task_scorer: computes the priority of the tasks in the pq
+/// - last_scored_position: the position of the robot when the pq was last scored
+/// - status: the position, energy and backpack of the robot, read again after every interaction with the world
+/// - rng: the random number generator used for every random decision of the robot, the same seed and world produce the same run
 pub struct TrashinatorRobot {
     pub robot: Robot,
     pub state: Rc<RefCell<AiState>>,
-    pub(crate) pq: PriorityQueue<Task, usize>,
-    pub(crate) current_task: Option<Task>,
+    pub pq: PriorityQueue<Task, usize>,
+    pub current_task: Option<Task>,
+    pub(crate) route: VecDeque<RouteStop>,
+    pub(crate) tasks_pushed_in_tick: Vec<(TaskAction, (usize, usize))>,
     pub(crate) marked_coords: HashSet<ChartedCoordinate>,
     pub(crate) charted_map: ChartedMap<TileType>,
+    pub(crate) known_map: Rc<RefCell<KnownMap>>,
+    pub(crate) bins: BinRegistry,
+    pub(crate) blacklist: HashSet<(usize, usize)>,
+    pub(crate) max_task_attempts: usize,
+    pub(crate) disposal_threshold: usize,
+    pub(crate) energy_policy: EnergyPolicy,
+    pub(crate) resting: bool,
+    pub(crate) exploration_path: Option<VecDeque<(usize, usize)>>,
+    pub(crate) exploration_complete: bool,
     pub(crate) previous_move_direction: Option<Direction>,
-    pub(crate) previous_one_directional_view_direction: Option<Direction>,
+    pub(crate) ticks: usize,
     pub(crate) tasks_completed: usize,
-    pub(crate) tasks_to_complete: usize
+    pub(crate) garbage_collected: usize,
+    pub(crate) garbage_deposited: usize,
+    pub(crate) fires_extinguished: usize,
+    pub(crate) goal: Goal,
+    pub(crate) strategies: Strategies,
+    pub(crate) task_scorer: Box<dyn TaskScorer>,
+    pub(crate) last_scored_position: Option<(usize, usize)>,
+    pub(crate) status: RobotStatus,
+    pub(crate) rng: StdRng
 }
 
 impl TrashinatorRobot {
-    pub fn new(robot: Robot, state: Rc<RefCell<AiState>>, tasks_to_complete: usize) -> TrashinatorRobot {
+    pub fn new(
+        robot: Robot,
+        state: Rc<RefCell<AiState>>,
+        goal: Goal,
+        strategies: Strategies,
+        seed: u64,
+    ) -> TrashinatorRobot {
         TrashinatorRobot {
             robot,
             state,
             pq: PriorityQueue::new(),
             current_task: None,
+            route: VecDeque::new(),
+            tasks_pushed_in_tick: vec![],
             marked_coords: HashSet::new(),
             charted_map: ChartingTools::tool::<ChartedMap<TileType>>().unwrap(),
+            known_map: Rc::new(RefCell::new(KnownMap::new())),
+            bins: BinRegistry::new(),
+            blacklist: HashSet::new(),
+            max_task_attempts: DEFAULT_MAX_TASK_ATTEMPTS,
+            disposal_threshold: DEFAULT_DISPOSAL_THRESHOLD,
+            energy_policy: EnergyPolicy::default(),
+            resting: false,
+            exploration_path: None,
+            exploration_complete: false,
             previous_move_direction: None,
-            previous_one_directional_view_direction: None,
+            ticks: 0,
             tasks_completed: 0,
-            tasks_to_complete
+            garbage_collected: 0,
+            garbage_deposited: 0,
+            fires_extinguished: 0,
+            goal,
+            strategies,
+            task_scorer: Box::new(DefaultTaskScorer::default()),
+            last_scored_position: None,
+            status: RobotStatus::default(),
+            rng: StdRng::seed_from_u64(seed)
         }
     }
 }
 
 impl TrashinatorRobot {
+    /// Checks the goal, returning the simple goals that were met if the robot should terminate
+    pub(crate) fn check_goal(&self) -> Option<Vec<Goal>> {
+        let known_fires = self
+            .current_task
+            .iter()
+            .chain(self.pq.iter().map(|(task, _)| task))
+            .filter(|task| task.action == TaskAction::DestroyFire)
+            .count();
+
+        let explored = match self.known_map.borrow().size() {
+            Some(size) if size > 0 => self.known_map.borrow().known_count() as f64 * 100.0 / (size * size) as f64,
+            _ => 0.0,
+        };
+
+        let progress = GoalProgress {
+            tasks_completed: self.tasks_completed,
+            known_fires,
+            garbage_collected: self.garbage_collected,
+            garbage_deposited: self.garbage_deposited,
+            fires_extinguished: self.fires_extinguished,
+            explored,
+            exploration_complete: self.exploration_complete,
+            ticks: self.ticks,
+            energy: self.status.energy,
+        };
+
+        self.goal.met_goals(&progress)
+    }
+
+    /// Reads again the position, energy and backpack of the robot from the world
+    pub fn refresh_status(&mut self, world: &dyn WorldInterface) {
+        self.status = world.status(self);
+    }
+
+    /// Copies the robot position, energy, backpack and tasks in the state for visualizers
+    pub(crate) fn update_state(&self) {
+        let mut pending_tasks: Vec<TaskSnapshot> = self
+            .pq
+            .iter()
+            .map(|(task, priority)| TaskSnapshot::new(task, *priority))
+            .collect();
+        pending_tasks.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+        let mut state = self.state.borrow_mut();
+        state.tick = self.ticks;
+        state.robot_coordinates = self.status.position;
+        state.energy = self.status.energy;
+        state.backpack = self.status.backpack.clone();
+        state.current_task = self
+            .current_task
+            .as_ref()
+            .map(|task| self.snapshot(task));
+        state.pending_tasks = pending_tasks;
+        state.changed_tiles = self
+            .known_map
+            .borrow()
+            .changed_in_tick()
+            .map(|(tile, coordinates)| (tile.clone(), coordinates))
+            .collect();
+    }
+
     /// Discovers new tiles and populates the pq
-    pub(crate) fn discover_tiles_and_populate_pq(&mut self, world: &mut World) {
-        let mut bob_map = BobMap::init(world);
+    pub fn discover_tiles_and_populate_pq(&mut self, world: &mut dyn WorldInterface) {
+        let view = world.view_around(self);
+        self.refresh_status(world);
 
-        let view = bob_view(self, world, &mut bob_map);
+        for (tile, coordinates) in view.iter() {
+            self.state.borrow_mut().discovered_tiles.push((tile.clone(), *coordinates));
+            self.known_map.borrow_mut().insert(tile, *coordinates);
 
-        for row in view.iter() {
-            for col in row.iter() {
-                match &col.0 {
-                    None => {}
-                    Some(tile) => {
-                        self.state.borrow_mut().discovered_tiles.push((tile.clone(), (col.1, col.2)));
+            self.populate_pq(tile, *coordinates);
+        }
+    }
 
-                        self.populate_pq(tile,  (col.1, col.2));
-                    }
-                }
+    /// Chooses the scan that reveals the most unknown tiles of the world per energy
+    ///
+    /// Known tiles not observed in the last `stale_after` process ticks count as unknown, since their content might have changed.
+    /// Tiles in the direction of the current task, or of the frontier being explored, are worth `heading_weight` times more.
+    /// Returns `None` if no scan would reveal anything new
+    pub fn plan_scan(&self, config: &ScanConfig) -> Option<Scan> {
+        let position = self.status.position;
+
+        let heading = self
+            .current_task
+            .as_ref()
+            .map(|task| task.coordinates)
+            .or_else(|| self.exploration_path.as_ref().and_then(|path| path.back().copied()))
+            .and_then(|target| heading_towards(position, target));
+
+        let stale_before = self.ticks.saturating_sub(config.stale_after);
+        let is_worth_seeing = |tile: (usize, usize)| {
+            self.known_map.borrow().is_within_bounds(tile)
+                && self
+                    .known_map
+                    .borrow()
+                    .last_observed(tile)
+                    .map_or(true, |observed| observed < stale_before)
+        };
+
+        let mut scans = vec![Scan::Around];
+        for direction in DIRECTIONS {
+            for distance in 1..=config.max_distance {
+                scans.push(Scan::OneDirection(direction.clone(), distance));
             }
         }
-    }
 
-    /// Discovers new tiles using the one directional view and populates the pq
-    pub(crate) fn discover_tiles_one_direction_and_populate_pq(&mut self, world: &mut World) {
-        let direction = Self::calculate_random_direction_with_weighted_previous_direction(
-            &self.previous_one_directional_view_direction,
-        );
+        scans
+            .into_iter()
+            .map(|scan| {
+                let unknown = scan_coverage(position, &scan)
+                    .into_iter()
+                    .filter(|tile| is_worth_seeing(*tile))
+                    .count();
+                let weight = match (&scan, &heading) {
+                    (Scan::OneDirection(direction, _), Some(heading)) if direction == heading => config.heading_weight,
+                    _ => 1,
+                };
+                let cost = self.energy_policy.scan_cost(&scan).max(1);
 
-        let view = one_direction_view(self, world, direction.clone(), 4);
+                (scan, unknown * weight, cost)
+            })
+            .filter(|(_, information, _)| *information > 0)
+            // Compares information per energy without dividing, then prefers the scan revealing more
+            .max_by(|(_, a_information, a_cost), (_, b_information, b_cost)| {
+                (a_information * b_cost)
+                    .cmp(&(b_information * a_cost))
+                    .then(a_information.cmp(b_information))
+            })
+            .map(|(scan, information, cost)| {
+                debug!("Planned scan {:?}, information: {}, cost: {}", scan, information, cost);
+                scan
+            })
+    }
+
+    /// Discovers new tiles using the one directional view up to `distance` tiles away and populates the pq
+    pub fn discover_tiles_one_direction_and_populate_pq(
+        &mut self,
+        world: &mut dyn WorldInterface,
+        direction: Direction,
+        distance: usize,
+    ) {
+        let view = world.one_direction_view(self, direction.clone(), distance);
+        self.refresh_status(world);
 
         match view {
             Ok(view) => {
+                let position = self.status.position;
+
                 for (x, row_tiles) in view.iter().enumerate() {
                     for (y, tile) in row_tiles.iter().enumerate() {
-                        let (row, col) = match direction {
-                            Direction::Up => {
-                                let row = self.get_coordinate().get_row() - x - 1;
-                                let col = self.get_coordinate().get_col() + y - 1;
-
-                                (row, col)
-                            }
-                            Direction::Down => {
-                                let row = self.get_coordinate().get_row() + x + 1;
-                                let col = self.get_coordinate().get_col() + y - 1;
-
-                                (row, col)
-                            }
-                            Direction::Left => {
-                                let row = self.get_coordinate().get_row() + x - 1;
-                                let col = self.get_coordinate().get_col() - y - 1;
-
-                                (row, col)
-                            }
-                            Direction::Right => {
-                                let row = self.get_coordinate().get_row() + x - 1;
-                                let col = self.get_coordinate().get_col() + y + 1;
-
-                                (row, col)
-                            }
+                        let coordinates =
+                            one_direction_view_coordinates(position, &direction, (x, y), self.known_map.borrow().size());
+                        let Some((row, col)) = coordinates else {
+                            continue;
                         };
 
                         self.state.borrow_mut().discovered_tiles.push((tile.clone(), (row, col)));
+                        self.known_map.borrow_mut().insert(tile, (row, col));
                         self.populate_pq(tile, (row, col));
                     }
                 }
             }
-            Err(e) => error!("Failed to look in one direction: {:?}", e),
+            Err(e) => {
+                let error = self.lib_error(e, AiStage::Scan);
+                self.report_error(error);
+            }
         };
-
-        // let mut bob_map = BobMap::init(world);
-        // let view = bob_one_direction_view(self, world, direction.clone(), 4, &mut bob_map);
-        //
-        // match view {
-        //     Ok(view) => {
-        //         for row_tiles in view.iter() {
-        //             for tile in row_tiles.iter() {
-        //                 self.state.borrow_mut().discovered_tiles.push((tile.0.clone(), (tile.1, tile.2)));
-        //                 self.populate_pq(&tile.0, (tile.1, tile.2));
-        //             }
-        //         }
-        //     }
-        //     Err(e) => error!("Failed to look in one direction: {:?}", e),
-        // };
     }
 
     /// Calculates the current task to execute
-    pub(crate) fn determine_current_task(&mut self) {
+    ///
+    /// When the task with the highest priority is a garbage task, the next stop of the garbage route is used instead
+    pub fn determine_current_task(&mut self) {
         if self.current_task.is_none() {
-            let new_task = self.pq.pop().map(|(task, _)| task);
+            let top_is_garbage = self
+                .pq
+                .peek()
+                .map_or(false, |(task, _)| task.action == TaskAction::DestroyGarbage);
+            let route_task = if top_is_garbage { self.next_route_task() } else { None };
+
+            let new_task = route_task.or_else(|| self.pq.pop().map(|(task, _)| task));
             self.current_task = new_task;
         }
 
@@ -156,162 +346,693 @@ impl TrashinatorRobot {
         }
     }
 
-    /// Executes the current task
-    pub(crate) fn execute_task(&mut self, world: &mut World) {
-        let current_task = &self.current_task;
+    /// Scores a task estimating its distance and energy cost from the Manhattan distance, without planning a path
+    pub(crate) fn estimate_task_score(&self, action: &TaskAction, coordinates: (usize, usize)) -> usize {
+        let distance = manhattan_distance(self.status.position, coordinates);
 
-        match current_task {
-            None => {
-                let current_coordinates = self.get_coordinate();
-                let current_row = current_coordinates.get_row();
-                let current_col = current_coordinates.get_col();
-
-                let teleports = self.charted_map.get(&TileType::Teleport(true));
-                let mut target_telepor_coordinates = None;
-
-                if let Some(teleports) = teleports {
-                    if teleports
-                        .iter()
-                        .any(|t| t.0 .0 == current_row && t.0 .1 == current_col)
-                    {
-                        if let Some(target_teleport) = teleports
-                            .iter()
-                            .find(|t| t.0 .0 != current_row || t.0 .1 != current_col)
-                        {
-                            target_telepor_coordinates =
-                                Some((target_teleport.0 .1, target_teleport.0 .1));
-                        }
-                    }
+        self.task_scorer.score(&TaskScoreInput {
+            action,
+            coordinates,
+            distance,
+            energy_cost: distance * UNKNOWN_TILE_COST,
+        })
+    }
+
+    /// Scores again every task in the pq using the path distance and energy cost from the current position
+    ///
+    /// Does nothing if the robot hasn't moved and no task has been added since the last time
+    pub fn rescore_pq(&mut self) {
+        let position = self.status.position;
+
+        if self.last_scored_position == Some(position) && self.tasks_pushed_in_tick.is_empty() {
+            return;
+        }
+        self.last_scored_position = Some(position);
+
+        let targets: Vec<(usize, usize)> = self.pq.iter().map(|(task, _)| task.coordinates).collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        // A single bounded search for all the tasks, a search per task would flood the world for each unreachable one
+        let teleports = self.known_teleports();
+        let costs = pathfinding::path_costs_to_targets(
+            &self.known_map.borrow(),
+            &teleports,
+            position,
+            &targets,
+            RESCORE_MAX_EXPANSIONS,
+        );
+
+        let scores: Vec<(Task, usize)> = self
+            .pq
+            .iter()
+            .map(|(task, _)| {
+                // Tasks that weren't reached by the search are estimated from the Manhattan distance
+                let (distance, energy_cost) = costs.get(&task.coordinates).copied().unwrap_or_else(|| {
+                    let distance = manhattan_distance(position, task.coordinates);
+                    (distance, distance * UNKNOWN_TILE_COST)
+                });
+
+                let score = self.task_scorer.score(&TaskScoreInput {
+                    action: &task.action,
+                    coordinates: task.coordinates,
+                    distance,
+                    energy_cost,
+                });
+
+                (Task::new(task.action.clone(), task.coordinates), score)
+            })
+            .collect();
+
+        for (task, score) in scores {
+            self.pq.change_priority(&task, score);
+        }
+    }
+
+    /// Copies a task with the priority computed by the `TaskScorer`, so that it can be compared with the tasks in the pq
+    pub(crate) fn snapshot(&self, task: &Task) -> TaskSnapshot {
+        TaskSnapshot::new(task, self.estimate_task_score(&task.action, task.coordinates))
+    }
+
+    /// Amount of garbage in the backpack
+    pub(crate) fn garbage_in_backpack(&self) -> usize {
+        self.status.backpack.get(&Garbage(0)).copied().unwrap_or(0)
+    }
+
+    /// Space still free in the backpack
+    pub(crate) fn free_backpack_space(&self) -> usize {
+        let used_space: usize = self.status.backpack.values().sum();
+
+        self.status.backpack_size.saturating_sub(used_space)
+    }
+
+    /// Amount of garbage last seen at the given coordinates, 1 if unknown
+    pub(crate) fn garbage_amount_at(&self, coordinates: (usize, usize)) -> usize {
+        match self.known_map.borrow().get(coordinates).map(|tile| &tile.content) {
+            Some(Garbage(amount)) => *amount,
+            _ => 1,
+        }
+    }
+
+    /// Makes room in the backpack for the garbage of the current task
+    ///
+    /// If the garbage at the target tile doesn't fit in the backpack, the current task is put back in the pq
+    /// and replaced by a trip to the closest bin that can accept the garbage in the backpack
+    pub fn ensure_backpack_space(&mut self) {
+        let coordinates = match &self.current_task {
+            Some(task) if task.action == TaskAction::DestroyGarbage => task.coordinates,
+            _ => return,
+        };
+
+        let amount = self.garbage_amount_at(coordinates);
+        let free_space = self.free_backpack_space();
+        let garbage = self.garbage_in_backpack();
+
+        // Nothing can be done if the space is taken by something other than garbage
+        if amount <= free_space || garbage == 0 {
+            return;
+        }
+
+        let position = self.status.position;
+
+        match self.bins.closest_with_capacity(position, garbage) {
+            Some(bin) => {
+                info!(
+                    "Backpack has space for {} but the garbage at {:?} is {}, emptying it in the bin at {:?} first",
+                    free_space, coordinates, amount, bin
+                );
+
+                if let Some(task) = self.current_task.take() {
+                    let priority = self.estimate_task_score(&task.action, task.coordinates);
+                    self.pq.push(task, priority);
                 }
 
-                if let Some(coordinates) = target_telepor_coordinates {
-                    let teleport_res = teleport(self, world, coordinates);
+                let bin_task = self.new_task(TaskAction::PutGarbageInBin, bin);
+                self.pq.remove(&bin_task);
+                self.current_task = Some(bin_task);
+            }
+            None => debug!("Backpack is full but no known bin can accept its garbage"),
+        }
+    }
 
-                    match teleport_res {
-                        Ok(_) => {
-                            debug!(
-                                "Teleported to coordinates {}, {}",
-                                coordinates.0, coordinates.1
-                            );
-                            return;
-                        }
-                        Err(e) => error!("Failed to teleport: {:?}", e),
+    /// Adds a task to empty the backpack in the closest bin that can accept its garbage
+    ///
+    /// The task is added only when the garbage in the backpack is above the `disposal_threshold`
+    /// and no disposal task is already pending
+    pub fn plan_disposal(&mut self) {
+        let garbage = self.garbage_in_backpack();
+
+        if garbage <= self.disposal_threshold {
+            return;
+        }
+
+        let is_disposal_pending = self
+            .current_task
+            .iter()
+            .chain(self.pq.iter().map(|(task, _)| task))
+            .any(|task| task.action == TaskAction::PutGarbageInBin);
+
+        if is_disposal_pending {
+            return;
+        }
+
+        let position = self.status.position;
+
+        match self.bins.closest_with_capacity(position, garbage) {
+            Some(bin) => {
+                let priority = self.estimate_task_score(&TaskAction::PutGarbageInBin, bin);
+                let task = self.new_task(TaskAction::PutGarbageInBin, bin);
+
+                debug!("Added task to pq: {:?}", task);
+
+                self.tasks_pushed_in_tick.push((task.action.clone(), task.coordinates));
+                self.pq.push(task, priority);
+            }
+            None => debug!("Backpack has {} garbage but no known bin can accept it", garbage),
+        }
+    }
+
+    /// Plans the order in which the garbage tasks in the pq are performed
+    ///
+    /// The route is planned again only when garbage tasks were added in this process tick or when the previous route is over.
+    /// Bin visits are added where the collected garbage wouldn't fit in the backpack anymore
+    pub fn plan_garbage_route(&mut self) {
+        let garbage_added = self
+            .tasks_pushed_in_tick
+            .iter()
+            .any(|(action, _)| *action == TaskAction::DestroyGarbage);
+
+        if !garbage_added && !self.route.is_empty() {
+            return;
+        }
+
+        let position = self.status.position;
+
+        let stops: Vec<(usize, usize)> = self
+            .pq
+            .iter()
+            .filter(|(task, _)| task.action == TaskAction::DestroyGarbage)
+            .map(|(task, _)| task.coordinates)
+            .collect();
+
+        if stops.is_empty() {
+            self.route.clear();
+            return;
+        }
+
+        let ordered_stops = order_stops(position, &stops);
+
+        let loads: Vec<usize> = ordered_stops
+            .iter()
+            .map(|stop| self.garbage_amount_at(*stop))
+            .collect();
+
+        let bins = self.bins.capacities();
+
+        self.route = insert_bin_visits(
+            position,
+            &ordered_stops,
+            &loads,
+            self.free_backpack_space(),
+            self.status.backpack_size,
+            &bins,
+        )
+        .into();
+
+        debug!("Planned garbage route: {:?}", self.route);
+    }
+
+    /// Returns the task for the next stop of the garbage route, removing it from the pq if needed
+    ///
+    /// Stops whose task isn't in the pq anymore are skipped, bin stops are skipped if the backpack has no garbage
+    fn next_route_task(&mut self) -> Option<Task> {
+        while let Some(stop) = self.route.pop_front() {
+            match stop {
+                RouteStop::Task(coordinates) => {
+                    let key = Task::new(TaskAction::DestroyGarbage, coordinates);
+
+                    if let Some((task, _)) = self.pq.remove(&key) {
+                        return Some(task);
                     }
                 }
+                RouteStop::Bin(coordinates) => {
+                    if self.garbage_in_backpack() > 0 {
+                        return Some(self.new_task(TaskAction::PutGarbageInBin, coordinates));
+                    }
+                }
+            }
+        }
 
-                let direction = Self::calculate_random_direction_with_weighted_previous_direction(
-                    &self.previous_move_direction,
-                );
-                let go_res = go(self, world, direction.clone());
+        None
+    }
+
+    /// Counts the current task as completed
+    fn record_task_completed(&mut self) {
+        self.tasks_completed += 1;
+        self.state.borrow_mut().task_stats.completed += 1;
+
+        if let Some(task) = &self.current_task {
+            self.unmark(task.coordinates);
+        }
+    }
+
+    /// Removes the coordinates from the `marked_coords`, so that a task can be created again for them
+    fn unmark(&mut self, coordinates: (usize, usize)) {
+        self.marked_coords.remove(&ChartedCoordinate::new(coordinates.0, coordinates.1));
+    }
+
+    /// Creates a task in the current process tick
+    fn new_task(&self, action: TaskAction, coordinates: (usize, usize)) -> Task {
+        let mut task = Task::new(action, coordinates);
+        task.created_tick = self.ticks;
+
+        task
+    }
+
+    /// Whether the target tile of the task was observed since the task was created without the content the task expects
+    ///
+    /// Tiles seen while moving are observed after the scheduling stage, so they are checked in the following process tick
+    fn is_task_stale(&self, task: &Task) -> bool {
+        let observed_since_created = self
+            .known_map
+            .borrow()
+            .last_observed(task.coordinates)
+            .map_or(false, |observed| observed >= task.created_tick);
+
+        if !observed_since_created {
+            return false;
+        }
+
+        let known_map = self.known_map.borrow();
+        let content = known_map.get(task.coordinates).map(|tile| &tile.content);
+
+        !matches!(
+            (&task.action, content),
+            (TaskAction::DestroyGarbage, Some(Garbage(_)))
+                | (TaskAction::DestroyFire, Some(Fire))
+                | (TaskAction::PutGarbageInBin, Some(Bin(_)))
+        )
+    }
+
+    /// Drops the tasks whose target tile was observed since they were created without the expected content
+    ///
+    /// The dropped coordinates are analyzed again, so that a task is created if they now hold a different content
+    pub fn validate_tasks(&mut self) {
+        let mut stale_tasks: Vec<Task> = self
+            .pq
+            .iter()
+            .filter(|(task, _)| self.is_task_stale(task))
+            .map(|(task, _)| Task::new(task.action.clone(), task.coordinates))
+            .collect();
+
+        for task in stale_tasks.iter() {
+            self.pq.remove(task);
+        }
+
+        if self.current_task.as_ref().map_or(false, |task| self.is_task_stale(task)) {
+            if let Some(task) = self.current_task.take() {
+                stale_tasks.push(task);
+            }
+        }
+
+        for task in stale_tasks {
+            info!("Dropping stale task {}", task);
+
+            let snapshot = self.snapshot(&task);
+            self.state
+                .borrow_mut()
+                .ai_events_of_tick
+                .push(AiEvent::StaleTaskDropped(snapshot));
+
+            self.unmark(task.coordinates);
+
+            let tile = self.known_map.borrow().get(task.coordinates).cloned();
+            if let Some(tile) = tile {
+                self.populate_pq(&tile, task.coordinates);
+            }
+        }
+    }
+
+    /// Handles the failure of the current task
+    ///
+    /// Tasks that failed for a transient reason go back in the pq until they run out of attempts,
+    /// failing for lack of energy doesn't use an attempt.
+    /// Tasks that are still unreachable after the last attempt have their coordinates blacklisted.
+    /// Disposal tasks refused by a full bin are dropped and the bin is marked as full
+    pub(crate) fn handle_task_failure(&mut self, failure: TaskFailure) {
+        let mut task = match self.current_task.take() {
+            None => return,
+            Some(task) => task,
+        };
+
+        // The step that failed might be part of the cached path
+        task.invalidate_path();
+
+        if failure.uses_attempt() {
+            task.attempts += 1;
+        }
+
+        // A bin without space is not tried again, the scheduling chooses another one
+        let is_bin_full = task.action == TaskAction::PutGarbageInBin && failure == TaskFailure::NotEnoughSpace;
+        if is_bin_full {
+            info!("Bin at {:?} is full", task.coordinates);
+            self.bins.mark_full(task.coordinates);
+        }
+
+        if failure.is_transient() && !is_bin_full && task.attempts < self.max_task_attempts {
+            info!(
+                "Task {} failed ({:?}), retrying later (attempt {}/{})",
+                task, failure, task.attempts, self.max_task_attempts
+            );
+            self.state.borrow_mut().task_stats.retried += 1;
+
+            let priority = self.estimate_task_score(&task.action, task.coordinates);
+            self.pq.push(task, priority);
+            return;
+        }
+
+        info!("Task {} failed ({:?}), dropping it", task, failure);
+        self.state.borrow_mut().task_stats.dropped += 1;
+        self.unmark(task.coordinates);
+
+        if failure == TaskFailure::Unreachable {
+            info!("Blacklisted unreachable coordinates {:?}", task.coordinates);
+            self.blacklist.insert(task.coordinates);
+            self.bins.remove(task.coordinates);
+            self.state.borrow_mut().task_stats.blacklisted += 1;
+        }
+    }
+
+    /// Wraps an error returned by `robotics_lib` with the current task and the coordinates of the robot
+    fn lib_error(&self, error: LibError, stage: AiStage) -> AiError {
+        AiError::Lib {
+            error,
+            stage,
+            task: self.current_task.as_ref().map(|task| self.snapshot(task)),
+            coordinates: self.status.position,
+        }
+    }
+
+    /// Logs the error and keeps it in the state, dropping the oldest one if there are already `MAX_LAST_ERRORS`
+    pub(crate) fn report_error(&mut self, error: AiError) {
+        error!("{}", error);
+
+        let mut state = self.state.borrow_mut();
+        if state.last_errors.len() >= MAX_LAST_ERRORS {
+            state.last_errors.pop_front();
+        }
+        state.last_errors.push_back(error);
+    }
+
+    /// Puts the current task back in the pq if a task added in this process tick should be performed before it
+    ///
+    /// A new task preempts the current one if its priority is higher by at least `priority_margin`, or if its priority
+    /// is not lower and it's at least `closer_ratio` times closer to the robot
+    pub fn preempt_current_task(&mut self, config: &PreemptionConfig) {
+        let position = self.status.position;
+
+        let (current_priority, current_distance) = match &self.current_task {
+            None => return,
+            Some(task) => (
+                task.action.get_priority_for_task(),
+                manhattan_distance(position, task.coordinates),
+            ),
+        };
+
+        let candidate = self
+            .tasks_pushed_in_tick
+            .iter()
+            .filter(|(action, coordinates)| {
+                let priority = action.get_priority_for_task();
+                let distance = manhattan_distance(position, *coordinates);
+
+                priority >= current_priority + config.priority_margin
+                    || (priority >= current_priority && distance * config.closer_ratio <= current_distance)
+            })
+            .max_by_key(|(action, coordinates)| {
+                (action.get_priority_for_task(), Reverse(manhattan_distance(position, *coordinates)))
+            })
+            .map(|(action, coordinates)| Task::new(action.clone(), *coordinates));
 
-                match go_res {
-                    Ok(_) => debug!("Moved {:?}", direction),
-                    Err(e) => {
-                        error!("Failed go to direction {:?}: {:?}", direction, e);
+        // The candidate might have already been popped from the pq
+        if let Some((new_task, new_priority)) = candidate.and_then(|candidate| self.pq.remove(&candidate)) {
+            if let Some(preempted) = self.current_task.take() {
+                info!("Task {} preempted by {}", preempted, new_task);
+
+                let priority = self.estimate_task_score(&preempted.action, preempted.coordinates);
+                self.state.borrow_mut().ai_events_of_tick.push(AiEvent::TaskPreempted {
+                    preempted: TaskSnapshot::new(&preempted, priority),
+                    by: TaskSnapshot::new(&new_task, new_priority),
+                });
+
+                self.pq.push(preempted, priority);
+            }
+
+            self.current_task = Some(new_task);
+        }
+    }
+
+    /// Decides whether the robot rests in this process tick to regenerate energy, and reports it in the state
+    ///
+    /// The robot rests when its energy isn't enough to reach the current task and perform its action, or to scan and take a
+    /// step while exploring, without going below the reserve of the `energy_policy`.
+    /// Unreachable tasks don't make the robot rest, their failure is handled when moving
+    pub fn update_rest_mode(&mut self) -> bool {
+        let energy = self.status.energy;
+
+        // The path is cached in the task, so the movement in the same process tick doesn't search it again
+        self.plan_current_task_path();
+
+        let cost = match &self.current_task {
+            Some(task) => {
+                self.estimate_energy_cost_to_task(task).unwrap_or(0) + self.energy_policy.action_cost(&task.action)
+            }
+            None => self.energy_policy.scan_cost(&Scan::Around) + UNKNOWN_TILE_COST,
+        };
+        let required = self.energy_policy.required_energy(cost);
+        let resting = energy < required;
+
+        if resting && !self.resting {
+            info!("Resting until the energy reaches {}, currently {}", required, energy);
+        } else if !resting && self.resting {
+            info!("Done resting with energy {}", energy);
+        }
+
+        self.resting = resting;
+        self.state.borrow_mut().resting = resting;
+
+        resting
+    }
+
+    /// Executes the current task
+    pub fn execute_task(&mut self, world: &mut dyn WorldInterface) {
+        match self.current_task {
+            None => {
+                let step = match self.determine_exploration_step() {
+                    Some(step) => step,
+                    None => {
+                        debug!("No reachable frontier, moving randomly");
+
+                        Step::Go(Self::calculate_random_direction_with_weighted_previous_direction(
+                            &mut self.rng,
+                            &self.previous_move_direction,
+                        ))
                     }
                 };
-            }
-            Some(task) => match self.determine_action_to_perform_task(task) {
-                Ok((execute, direction)) => {
-                    debug!(
-                        "Determined action to perform, execute: {}, direction: {:?}",
-                        execute, direction
-                    );
-
-                    if execute {
-                        match task.action {
-                            TaskAction::PutGarbageInBin => {
-                                if let Some(garbage) =
-                                    self.get_backpack().get_contents().get(&Garbage(0))
-                                {
-                                    if *garbage > 0 {
-                                        let res = put(
-                                            self,
-                                            world,
-                                            Garbage(0),
-                                            *garbage,
-                                            direction.clone(),
-                                        );
-
-                                        match res {
-                                            Ok(_) => {
-                                                self.tasks_completed += 1;
-                                                info!("Put garbage in bin at {:?}", direction);
-                                            }
-                                            Err(e) => error!(
-                                                "Failed putting garbage in bin at {:?}: {:?}",
-                                                direction, e
-                                            ),
-                                        }
-                                    }
-                                }
+
+                match self.take_step(world, step, AiStage::Exploration) {
+                    Ok(coordinates) => {
+                        if let Some(path) = self.exploration_path.as_mut() {
+                            if path.front() == Some(&coordinates) {
+                                path.pop_front();
                             }
-                            _ => {
-                                let res = destroy(self, world, direction.clone());
+                        }
+                    }
+                    Err(error) => {
+                        self.report_error(error);
+                        self.exploration_path = None;
+                    }
+                };
+            }
+            Some(_) => match self.determine_action_to_perform_task() {
+                Ok(NextMove::Perform(direction)) => {
+                    debug!("Performing the current task, direction: {:?}", direction);
+
+                    let action = self.current_task.as_ref().map(|task| task.action.clone());
+                    let mut failure = None;
+
+                    match action {
+                        Some(TaskAction::PutGarbageInBin) => {
+                            let garbage = self.garbage_in_backpack();
+
+                            if garbage > 0 {
+                                let res = world.put(self, Garbage(0), garbage, direction.clone());
+                                self.refresh_status(world);
 
                                 match res {
-                                    Ok(_) => {
-                                        self.tasks_completed += 1;
-                                        info!("Destroyed {:?}", direction);
+                                    Ok(amount) => {
+                                        self.record_task_completed();
+                                        self.garbage_deposited += amount;
+                                        info!("Put {} garbage in bin at {:?}", amount, direction);
+
+                                        if let Some(task) = &self.current_task {
+                                            self.bins.consume(task.coordinates, amount);
+                                        }
+                                    }
+                                    Err(e) => {
+                                        let error = self.lib_error(e, AiStage::Action);
+                                        failure = Some(TaskFailure::from(&error));
+                                        self.report_error(error);
                                     }
-                                    Err(e) => error!("Failed destroy at {:?}: {:?}", direction, e),
                                 }
                             }
-                        };
+                        }
+                        _ => {
+                            let res = world.destroy(self, direction.clone());
+                            self.refresh_status(world);
 
-                        self.current_task = None;
-                    } else {
-                        let res = go(self, world, direction.clone());
+                            match res {
+                                // Garbage tasks are complete only if something was collected
+                                Ok(0) if action == Some(TaskAction::DestroyGarbage) => {
+                                    info!("Nothing collected destroying garbage at {:?}", direction);
+                                    failure = Some(TaskFailure::NothingCollected);
+                                }
+                                Ok(amount) => {
+                                    self.record_task_completed();
 
-                        match res {
-                            Ok(_) => {
-                                debug!("Moved {:?}", direction);
+                                    match action {
+                                        Some(TaskAction::DestroyGarbage) => self.garbage_collected += amount,
+                                        Some(TaskAction::DestroyFire) => self.fires_extinguished += 1,
+                                        _ => {}
+                                    }
+                                    info!("Destroyed {:?}", direction);
+                                }
+                                Err(e) => {
+                                    let error = self.lib_error(e, AiStage::Action);
+                                    failure = Some(TaskFailure::from(&error));
+                                    self.report_error(error);
+                                }
                             }
-                            Err(e) => error!("Failed go to {:?}: {:?}", direction, e),
                         }
                     };
+
+                    match failure {
+                        None => self.current_task = None,
+                        Some(failure) => self.handle_task_failure(failure),
+                    }
+                }
+                Ok(NextMove::Step(step)) => match self.take_step(world, step, AiStage::Navigation) {
+                    Ok(_) => {
+                        if let Some(path) = self.current_task.as_mut().and_then(|task| task.path.as_mut()) {
+                            path.pop_front();
+                        }
+                    }
+                    Err(error) => {
+                        let failure = TaskFailure::from(&error);
+                        self.report_error(error);
+                        self.handle_task_failure(failure);
+                    }
+                },
+                Err(error) => {
+                    let failure = TaskFailure::from(&error);
+                    self.report_error(error);
+                    self.handle_task_failure(failure);
                 }
-                Err(_) => debug!("Failed determining task to perform"),
             },
         }
     }
 
+    /// Walks or teleports to the next tile of a path, recording the tiles seen on arrival and the direction of the move
+    ///
+    /// Returns the coordinates of the robot after the step, a failed teleport is reported with the `Teleport` stage
+    fn take_step(
+        &mut self,
+        world: &mut dyn WorldInterface,
+        step: Step,
+        stage: AiStage,
+    ) -> Result<(usize, usize), AiError> {
+        let result = match &step {
+            Step::Go(direction) => world.go(self, direction.clone()).map_err(|e| self.lib_error(e, stage)),
+            Step::Teleport(coordinates) => {
+                world.teleport(self, *coordinates).map_err(|e| self.lib_error(e, AiStage::Teleport))
+            }
+        };
+        self.refresh_status(world);
+
+        let (view, coordinates) = result?;
+        debug!("Took step {:?}, now at {:?}", step, coordinates);
+
+        // The random walk avoids going back where the robot came from
+        self.previous_move_direction = match step {
+            Step::Go(direction) => Some(direction),
+            Step::Teleport(_) => None,
+        };
+
+        self.record_surroundings(&view, coordinates);
+
+        Ok(coordinates)
+    }
+
+    /// Returns the coordinates of the known teleports the robot can teleport to, sorted to keep runs reproducible
+    fn known_teleports(&self) -> Vec<(usize, usize)> {
+        let mut teleports: Vec<(usize, usize)> = self
+            .charted_map
+            .get(&TileType::Teleport(true))
+            .map(|teleports| teleports.iter().map(|t| (t.0 .0, t.0 .1)).collect())
+            .unwrap_or_default();
+        teleports.sort();
+
+        teleports
+    }
+
+    /// Determines the step to take towards the nearest frontier of the known map, teleporting when it's cheaper
+    ///
+    /// The path to the frontier is cached and planned again only when it can't be followed anymore.
+    /// Returns `None` if no frontier is reachable, in which case the robot should fall back to a random walk
+    fn determine_exploration_step(&mut self) -> Option<Step> {
+        let current = self.status.position;
+        let teleports = self.known_teleports();
+
+        let is_path_valid = self
+            .exploration_path
+            .as_ref()
+            .map_or(false, |path| is_exploration_path_valid(&self.known_map.borrow(), &teleports, current, path));
+
+        if !is_path_valid {
+            self.exploration_path = find_nearest_frontier(&self.known_map.borrow(), &teleports, current);
+            debug!("Planned exploration path: {:?}", self.exploration_path);
+
+            if self.exploration_path.is_none() && self.known_map.borrow().known_count() > 0 && !self.exploration_complete {
+                info!("Every reachable tile has been explored");
+                self.exploration_complete = true;
+            }
+        }
+
+        self.exploration_path
+            .as_ref()
+            .and_then(|path| path.front())
+            .and_then(|next| step_towards(&teleports, current, *next))
+    }
+
     /// Calculates a direction in mix of deterministic and random logic based on the previously used `Direction`
+    ///
+    /// The randomness comes from the given `rng` so that runs can be reproduced
     fn calculate_random_direction_with_weighted_previous_direction(
+        rng: &mut StdRng,
         previous: &Option<Direction>,
     ) -> Direction {
-        let left = if *previous == Some(Direction::Right) {
-            50
-        } else {
-            100
-        };
-        let right = if *previous == Some(Direction::Left) {
-            50
-        } else {
-            100
-        };
-        let up = if *previous == Some(Direction::Down) {
-            50
-        } else {
-            100
-        };
-        let down = if *previous == Some(Direction::Up) {
-            50
-        } else {
-            100
-        };
+        let going_back = previous.as_ref().map(|previous| previous.opposite());
 
-        let left_random = rand::thread_rng().gen_range(0..left);
-        let right_random = rand::thread_rng().gen_range(0..right);
-        let up_random = rand::thread_rng().gen_range(0..up);
-        let down_random = rand::thread_rng().gen_range(0..down);
+        // Going back where the robot came from is half as likely
+        let vec_of_randoms: Vec<(i32, Direction)> = [Direction::Left, Direction::Right, Direction::Up, Direction::Down]
+            .into_iter()
+            .map(|direction| {
+                let weight = if going_back.as_ref() == Some(&direction) { 50 } else { 100 };
 
-        let vec_of_randoms = vec![
-            (left_random, Direction::Left),
-            (right_random, Direction::Right),
-            (up_random, Direction::Up),
-            (down_random, Direction::Down),
-        ];
+                (rng.gen_range(0..weight), direction)
+            })
+            .collect();
 
         let mut max = -1;
         let mut direction = Direction::Left;
@@ -337,21 +1058,19 @@ impl TrashinatorRobot {
             debug!("Saved teleport tile at coordinates {}", charted_coordinates)
         }
 
+        if self.blacklist.contains(&coordinate) {
+            return;
+        }
+
+        match &tile.content {
+            Bin(range) => self.bins.record(coordinate, range),
+            _ => self.bins.remove(coordinate),
+        }
+
+        // Disposal tasks are created by `plan_disposal` using the bins recorded above
         let action = match tile.content {
             Garbage(_) => Some(TaskAction::DestroyGarbage),
             Fire => Some(TaskAction::DestroyFire),
-            Bin(_) => self
-                .get_backpack()
-                .get_contents()
-                .get(&Garbage(0))
-                .map(|garbage| {
-                    if *garbage > 5 {
-                        Some(TaskAction::PutGarbageInBin)
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or(None),
             _ => None,
         };
 
@@ -359,11 +1078,12 @@ impl TrashinatorRobot {
             if !self.marked_coords.contains(charted_coordinates) {
                 self.marked_coords.insert(charted_coordinates.clone());
 
-                let priority = action.get_priority_for_task();
-                let task = Task::new(action, (coordinate.0, coordinate.1));
+                let priority = self.estimate_task_score(&action, coordinate);
+                let task = self.new_task(action, (coordinate.0, coordinate.1));
 
                 debug!("Added task to pq: {:?}", task);
 
+                self.tasks_pushed_in_tick.push((task.action.clone(), task.coordinates));
                 self.pq.push(task, priority);
             }
         } else {
@@ -371,65 +1091,324 @@ impl TrashinatorRobot {
         }
     }
 
-    /// Determines the action that the robot needs to perform in order to get closer to the
-    /// completion of the task
+    /// Stores the tiles around the robot returned by a movement in the known map and populates the pq
     ///
-    /// Returns a Result containing:
-    /// - a `bool`: true if the bot should perform the task action, false if it should just move
-    /// - a `Direction` in which the robot should perform the action or move (depending on the bool)
-    fn determine_action_to_perform_task(&self, task: &Task) -> Result<(bool, Direction), ()> {
-        let current_coordinates = self.get_coordinate();
-        let current_row = current_coordinates.get_row();
-        let current_col = current_coordinates.get_col();
-
-        let row_diff = current_row as i32 - task.coordinates.0 as i32;
-        let col_diff = task.coordinates.1 as i32 - current_col as i32;
-
-        let mut direction_for_action = if row_diff.abs() > 1 {
-            if row_diff.is_positive() {
-                Some(Direction::Up)
-            } else {
-                Some(Direction::Down)
-            }
-        } else if col_diff.abs() > 1 {
-            if col_diff.is_positive() {
-                Some(Direction::Right)
-            } else {
-                Some(Direction::Left)
+    /// The view is a 3x3 grid centered on the given coordinates
+    fn record_surroundings(&mut self, view: &[Vec<Option<Tile>>], coordinates: (usize, usize)) {
+        for (x, row_tiles) in view.iter().enumerate() {
+            for (y, tile) in row_tiles.iter().enumerate() {
+                let tile_coordinates = offset(coordinates, (x as i64 - 1, y as i64 - 1))
+                    .and_then(|tile_coordinates| clip(tile_coordinates, self.known_map.borrow().size()));
+
+                if let (Some(tile), Some(tile_coordinates)) = (tile, tile_coordinates) {
+                    self.state.borrow_mut().discovered_tiles.push((tile.clone(), tile_coordinates));
+                    self.known_map.borrow_mut().insert(tile, tile_coordinates);
+                    self.populate_pq(tile, tile_coordinates);
+                }
             }
-        } else if row_diff.abs() == 1 && col_diff.abs() == 1 {
-            if col_diff.is_positive() {
-                Some(Direction::Right)
-            } else {
-                Some(Direction::Left)
+        }
+    }
+
+    /// Predicts the energy needed to reach a tile from which the task can be performed
+    ///
+    /// This is the estimate used to decide when to rest, custom strategies can use it to weigh tasks.
+    /// Returns `None` if the task is unreachable
+    pub fn estimate_energy_cost_to_task(&self, task: &Task) -> Option<usize> {
+        self.estimate_path_to_task(task).map(|(_, energy_cost)| energy_cost)
+    }
+
+    /// Predicts the steps and the energy needed to reach a tile from which the task can be performed
+    ///
+    /// Uses the search cached in the task if it's still valid, otherwise plans a new path without caching it.
+    /// Returns `None` if the task is unreachable
+    fn estimate_path_to_task(&self, task: &Task) -> Option<(usize, usize)> {
+        let current = self.status.position;
+        let revision = self.known_map.borrow().revision();
+
+        if direction_towards(current, task.coordinates).is_some() {
+            return Some((0, 0));
+        }
+
+        if task.unreachable_from == Some((current, revision)) {
+            return None;
+        }
+
+        let teleports = self.known_teleports();
+
+        match &task.path {
+            Some(path) if task.has_valid_path(&teleports, current, revision) => {
+                Some((path.len(), path_energy_cost(&self.known_map.borrow(), current, path)))
             }
-        } else {
-            None
+            _ => pathfinding::find_path(&self.known_map.borrow(), &teleports, current, task.coordinates)
+                .map(|path| (path.len(), path_energy_cost(&self.known_map.borrow(), current, &path))),
+        }
+    }
+
+    /// Plans the path to the current task, unless it's adjacent or the cached path is still valid
+    fn plan_current_task_path(&mut self) {
+        let current = self.status.position;
+        let teleports = self.known_teleports();
+        let known_map = self.known_map.borrow();
+
+        let Some(task) = self.current_task.as_mut() else {
+            return;
         };
 
-        return if direction_for_action.is_some() {
-            Ok((false, direction_for_action.unwrap()))
-        } else {
-            direction_for_action = if row_diff.abs() == 1 {
-                if row_diff.is_positive() {
-                    Some(Direction::Up)
-                } else {
-                    Some(Direction::Down)
-                }
-            } else if col_diff.abs() == 1 {
-                if col_diff.is_positive() {
-                    Some(Direction::Right)
-                } else {
-                    Some(Direction::Left)
-                }
-            } else {
-                None
-            };
+        if direction_towards(current, task.coordinates).is_some() {
+            return;
+        }
 
-            match direction_for_action {
-                Some(direction) => Ok((true, direction)),
-                None => Err(()),
-            }
+        if task.plan_path(&known_map, &teleports, current) {
+            debug!(
+                "Planned path for {}: {:?}, predicted energy cost: {:?}",
+                task,
+                task.path,
+                task.path.as_ref().map(|path| path_energy_cost(&known_map, current, path))
+            );
+        }
+    }
+
+    /// Determines the action that the robot needs to perform in order to get closer to the
+    /// completion of the current task
+    ///
+    /// The path to the task is planned with A* over the known map, minimizing the energy spent to walk it
+    /// and going through the known teleports when it's cheaper, and cached in the task,
+    /// it gets planned again only when new tiles have been discovered or when the cached path was invalidated
+    ///
+    /// Returns a Result containing the `NextMove`: performing the task action in a direction, or taking the next step of the path
+    ///
+    /// Fails with `AiError::Unreachable` if no path to the task exists in the known map
+    fn determine_action_to_perform_task(&mut self) -> Result<NextMove, AiError> {
+        let current = self.status.position;
+        let teleports = self.known_teleports();
+
+        self.plan_current_task_path();
+        let task = self.current_task.as_ref().ok_or(AiError::NoCurrentTask)?;
+
+        if let Some(direction) = direction_towards(current, task.coordinates) {
+            return Ok(NextMove::Perform(direction));
+        }
+
+        task.path
+            .as_ref()
+            .and_then(|path| path.front())
+            .and_then(|next| step_towards(&teleports, current, *next))
+            .map(NextMove::Step)
+            .ok_or_else(|| AiError::Unreachable {
+                task: self.snapshot(task),
+                coordinates: current,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use robotics_lib::world::tile::Content;
+    use crate::interface::fake::{FakeCall, FakeWorld};
+
+    fn robot_in(world: &FakeWorld) -> TrashinatorRobot {
+        let mut robot = TrashinatorRobot::new(
+            Robot::new(),
+            Rc::new(RefCell::new(AiState::new())),
+            Goal::TasksCompleted(10),
+            Strategies::default(),
+            0,
+        );
+        robot.known_map.borrow_mut().set_size(world.tiles.len());
+        robot.refresh_status(world);
+
+        robot
+    }
+
+    fn has_task(robot: &TrashinatorRobot, action: TaskAction, coordinates: (usize, usize)) -> bool {
+        robot
+            .pq
+            .iter()
+            .any(|(task, _)| task.action == action && task.coordinates == coordinates)
+    }
+
+    #[test]
+    fn populate_pq_from_the_view_around() {
+        let mut world = FakeWorld::grass(5, (2, 2));
+        world.set_content((1, 1), Fire);
+        world.set_content((2, 3), Garbage(2));
+        world.set_content((3, 2), Garbage(1));
+        world.set_content((3, 3), Bin(0..5));
+        let mut robot = robot_in(&world);
+        robot.blacklist.insert((3, 2));
+
+        robot.discover_tiles_and_populate_pq(&mut world);
+
+        assert_eq!(world.calls, vec![FakeCall::ViewAround]);
+        assert_eq!(robot.pq.len(), 2);
+        assert!(has_task(&robot, TaskAction::DestroyFire, (1, 1)));
+        assert!(has_task(&robot, TaskAction::DestroyGarbage, (2, 3)));
+        // Blacklisted coordinates never get a task
+        assert!(!has_task(&robot, TaskAction::DestroyGarbage, (3, 2)));
+        assert_eq!(robot.bins.capacities().iter().map(|(bin, _)| *bin).collect::<Vec<_>>(), vec![(3, 3)]);
+        assert_eq!(robot.known_map.borrow().known_count(), 9);
+
+        // Tasks are created only once for the same coordinates
+        robot.discover_tiles_and_populate_pq(&mut world);
+        assert_eq!(robot.pq.len(), 2);
+    }
+
+    #[test]
+    fn tasks_seen_while_moving_become_stale() {
+        let mut world = FakeWorld::grass(5, (0, 0));
+        world.set_content((1, 1), Fire);
+        let mut robot = robot_in(&world);
+
+        // The fire is seen after the first step, then it burns out and it's seen again while moving
+        robot.current_task = Some(Task::new(TaskAction::DestroyGarbage, (4, 4)));
+        robot.execute_task(&mut world);
+        assert!(has_task(&robot, TaskAction::DestroyFire, (1, 1)));
+
+        world.set_content((1, 1), Content::None);
+        robot.ticks = 1;
+        robot.known_map.borrow_mut().set_tick(1);
+        robot.execute_task(&mut world);
+
+        robot.ticks = 2;
+        robot.known_map.borrow_mut().set_tick(2);
+        robot.validate_tasks();
+        assert!(!has_task(&robot, TaskAction::DestroyFire, (1, 1)));
+    }
+
+    #[test]
+    fn determine_action_to_perform_task() {
+        let world = FakeWorld::grass(5, (0, 0));
+        let mut robot = robot_in(&world);
+
+        assert!(matches!(robot.determine_action_to_perform_task(), Err(AiError::NoCurrentTask)));
+
+        robot.current_task = Some(Task::new(TaskAction::DestroyFire, (1, 0)));
+        assert_eq!(robot.determine_action_to_perform_task().unwrap(), NextMove::Perform(Direction::Down));
+
+        robot.current_task = Some(Task::new(TaskAction::DestroyFire, (0, 3)));
+        assert_eq!(
+            robot.determine_action_to_perform_task().unwrap(),
+            NextMove::Step(Step::Go(Direction::Right))
+        );
+
+        // Every tile next to the task is known to be deep water
+        let water = Tile {
+            tile_type: TileType::DeepWater,
+            content: Content::None,
+            elevation: 0,
         };
+        robot.known_map.borrow_mut().insert(&water, (3, 4));
+        robot.known_map.borrow_mut().insert(&water, (4, 3));
+        robot.current_task = Some(Task::new(TaskAction::DestroyFire, (4, 4)));
+        assert!(matches!(
+            robot.determine_action_to_perform_task(),
+            Err(AiError::Unreachable { coordinates: (0, 0), .. })
+        ));
+    }
+
+    #[test]
+    fn execute_task_performs_and_walks() {
+        let mut world = FakeWorld::grass(5, (0, 0));
+        world.set_content((1, 0), Fire);
+        world.set_content((0, 3), Garbage(2));
+        let mut robot = robot_in(&world);
+
+        robot.current_task = Some(Task::new(TaskAction::DestroyFire, (1, 0)));
+        robot.execute_task(&mut world);
+
+        assert_eq!(world.calls, vec![FakeCall::Destroy(Direction::Down)]);
+        assert_eq!(world.tile((1, 0)).unwrap().content, Content::None);
+        assert_eq!(robot.tasks_completed, 1);
+        assert!(robot.current_task.is_none());
+
+        robot.current_task = Some(Task::new(TaskAction::DestroyGarbage, (0, 3)));
+        robot.execute_task(&mut world);
+        robot.execute_task(&mut world);
+        robot.execute_task(&mut world);
+
+        assert_eq!(
+            world.calls[1..],
+            [
+                FakeCall::Go(Direction::Right),
+                FakeCall::Go(Direction::Right),
+                FakeCall::Destroy(Direction::Right)
+            ]
+        );
+        assert_eq!(robot.status.position, (0, 2));
+        assert_eq!(robot.garbage_in_backpack(), 2);
+        assert_eq!(robot.garbage_collected, 2);
+        assert_eq!(robot.tasks_completed, 2);
+    }
+
+    #[test]
+    fn execute_task_failures_with_scripted_status() {
+        let mut world = FakeWorld::grass(5, (0, 0));
+        world.set_content((0, 1), Garbage(3));
+        world.status.energy = 0;
+        let mut robot = robot_in(&world);
+
+        // Without energy the task goes back in the pq without using an attempt
+        robot.current_task = Some(Task::new(TaskAction::DestroyGarbage, (0, 1)));
+        robot.execute_task(&mut world);
+
+        assert!(robot.current_task.is_none());
+        assert!(has_task(&robot, TaskAction::DestroyGarbage, (0, 1)));
+        assert_eq!(robot.pq.peek().unwrap().0.attempts, 0);
+        assert!(matches!(
+            robot.state.borrow().last_errors.back(),
+            Some(AiError::Lib { error: LibError::NotEnoughEnergy, stage: AiStage::Action, .. })
+        ));
+
+        // With a full backpack the task uses an attempt
+        world.status.energy = 1000;
+        world.status.backpack.insert(Garbage(0), 19);
+        robot.refresh_status(&world);
+        robot.determine_current_task();
+        robot.execute_task(&mut world);
+
+        assert_eq!(robot.free_backpack_space(), 1);
+        assert_eq!(robot.pq.peek().unwrap().0.attempts, 1);
+        assert!(matches!(
+            robot.state.borrow().last_errors.back(),
+            Some(AiError::Lib { error: LibError::NotEnoughSpace(3), .. })
+        ));
+        assert_eq!(world.tile((0, 1)).unwrap().content, Garbage(3));
+    }
+
+    #[test]
+    fn run_ticks_until_the_fire_is_found_and_destroyed() {
+        let mut world = FakeWorld::grass(5, (2, 0));
+        world.set_content((2, 4), Fire);
+        let mut robot = robot_in(&world);
+
+        for _ in 0..20 {
+            robot.run_tick(&mut world);
+
+            if robot.tasks_completed > 0 {
+                break;
+            }
+        }
+
+        assert_eq!(robot.tasks_completed, 1);
+        assert_eq!(world.tile((2, 4)).unwrap().content, Content::None);
+        assert_eq!(robot.state.borrow().robot_coordinates, world.status.position);
+    }
+
+    #[test]
+    fn full_bins_are_not_tried_again() {
+        let mut world = FakeWorld::grass(5, (0, 0));
+        world.set_content((0, 1), Bin(5..5));
+        world.status.backpack.insert(Garbage(0), 6);
+        let mut robot = robot_in(&world);
+        robot.bins.record((0, 1), &(0..5));
+
+        robot.current_task = Some(Task::new(TaskAction::PutGarbageInBin, (0, 1)));
+        robot.execute_task(&mut world);
+
+        assert!(robot.current_task.is_none());
+        assert!(robot.pq.is_empty());
+        assert!(robot.bins.capacities().is_empty());
+        assert_eq!(robot.garbage_in_backpack(), 6);
     }
 }
@@ -1,31 +1,62 @@
-use log::debug;
+use log::{debug, info};
 use robotics_lib::energy::Energy;
 use robotics_lib::event::events::Event;
 use robotics_lib::runner::backpack::BackPack;
 use robotics_lib::runner::Runnable;
 use robotics_lib::world::coordinates::Coordinate;
 use robotics_lib::world::World;
+use crate::interface::WorldInterface;
 use crate::robot::TrashinatorRobot;
 
-impl Runnable for TrashinatorRobot {
-    fn process_tick(&mut self, world: &mut World) {
-        let coordinates = self.get_coordinate();
-        debug!("Current coordinates: {:?}", coordinates);
+impl TrashinatorRobot {
+    /// Performs a process tick on any world: detection, scheduling and movement, then checks the goal
+    pub fn run_tick(&mut self, world: &mut dyn WorldInterface) {
+        self.refresh_status(world);
+        debug!("Current coordinates: {:?}", self.status.position);
+
+        if self.known_map.borrow().size().is_none() {
+            if let Some(map) = world.robot_map() {
+                self.known_map.borrow_mut().set_size(map.len());
+            }
+        }
 
-        let energy = self.get_energy().get_energy_level();
+        self.ticks += 1;
+        self.known_map.borrow_mut().set_tick(self.ticks);
+        self.tasks_pushed_in_tick.clear();
+
+        // The strategies are taken out of the robot so that they can borrow it mutably
+        let mut strategies = std::mem::take(&mut self.strategies);
+
+        // While resting the robot doesn't spend energy, so that it regenerates
+        if !self.resting {
+            strategies.detection.detect(self, world);
+            strategies.scheduling.schedule(self);
+        }
 
-        if energy > 50 && energy % 2 == 0 {
-            self.discover_tiles_one_direction_and_populate_pq(world);
+        if self.update_rest_mode() {
+            debug!("Resting with energy {}", self.status.energy);
         } else {
-            self.discover_tiles_and_populate_pq(world);
+            strategies.movement.execute(self, world);
         }
 
-        self.determine_current_task();
-        self.execute_task(world);
+        self.strategies = strategies;
+        self.refresh_status(world);
 
-        if self.tasks_completed >= self.tasks_to_complete {
-            self.state.borrow_mut().terminate = true;
+        if let Some(met_goals) = self.check_goal() {
+            info!("Goal met: {:?}", met_goals);
+
+            let mut state = self.state.borrow_mut();
+            state.terminate = true;
+            state.met_goals = met_goals;
         }
+
+        self.update_state();
+    }
+}
+
+impl Runnable for TrashinatorRobot {
+    fn process_tick(&mut self, world: &mut World) {
+        self.run_tick(world);
     }
 
     fn handle_event(&mut self, event: Event) {
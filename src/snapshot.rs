@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use robotics_lib::world::tile::{Content, Tile};
+use serde::{Deserialize, Serialize};
+use crate::models::known_map::KnownMap;
+use crate::state::AiState;
+
+/// The known map of the robot, or the part of it that changed since a tick, with the state of the robot
+///
+/// Properties:
+/// - tick: the number of process ticks performed by the robot, pass it to `WrapperTrashinatorRobot::known_map_diff` to get the following changes
+/// - tiles: the known tiles with their coordinates in the form of row, col
+/// - robot_coordinates: the coordinates of the robot, in the form of row, col
+/// - energy: the energy level of the robot
+/// - backpack: the contents of the backpack
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KnownMapSnapshot {
+    pub tick: usize,
+    pub tiles: Vec<(Tile, (usize, usize))>,
+    pub robot_coordinates: (usize, usize),
+    pub energy: usize,
+    pub backpack: HashMap<Content, usize>,
+}
+
+impl KnownMapSnapshot {
+    /// The whole known map
+    pub(crate) fn full(known_map: &KnownMap, state: &AiState) -> KnownMapSnapshot {
+        let tiles = known_map
+            .tiles()
+            .map(|(tile, coordinates)| (tile.clone(), coordinates))
+            .collect();
+
+        KnownMapSnapshot::new(known_map, tiles, state)
+    }
+
+    /// The tiles discovered or changed after the process tick `since_tick`
+    ///
+    /// Only the logged changes are visited, so the cost depends on the number of changes and not on the size of the map.
+    /// If the changes of those ticks are no longer logged the whole known map is returned
+    pub(crate) fn diff(known_map: &KnownMap, since_tick: usize, state: &AiState) -> KnownMapSnapshot {
+        let Some(changed) = known_map.changed_since(since_tick) else {
+            return KnownMapSnapshot::full(known_map, state);
+        };
+
+        let tiles = changed
+            .into_iter()
+            .filter_map(|coordinates| known_map.get(coordinates).map(|tile| (tile.clone(), coordinates)))
+            .collect();
+
+        KnownMapSnapshot::new(known_map, tiles, state)
+    }
+
+    fn new(known_map: &KnownMap, tiles: Vec<(Tile, (usize, usize))>, state: &AiState) -> KnownMapSnapshot {
+        KnownMapSnapshot {
+            tick: known_map.tick(),
+            tiles,
+            robot_coordinates: state.robot_coordinates,
+            energy: state.energy,
+            backpack: state.backpack.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::known_map::CHANGE_LOG_TICKS;
+    use robotics_lib::world::tile::TileType;
+
+    fn tile(content: Content) -> Tile {
+        Tile {
+            tile_type: TileType::Grass,
+            content,
+            elevation: 0,
+        }
+    }
+
+    fn coordinates(snapshot: &KnownMapSnapshot) -> Vec<(usize, usize)> {
+        let mut coordinates: Vec<(usize, usize)> = snapshot.tiles.iter().map(|(_, coordinates)| *coordinates).collect();
+        coordinates.sort();
+        coordinates
+    }
+
+    #[test]
+    fn diff_returns_the_tiles_changed_after_the_tick() {
+        let state = AiState::new();
+        let mut known_map = KnownMap::new();
+
+        known_map.set_tick(1);
+        known_map.insert(&tile(Content::None), (0, 0));
+        known_map.insert(&tile(Content::None), (0, 1));
+        known_map.set_tick(2);
+        known_map.insert(&tile(Content::Fire), (0, 1));
+        known_map.insert(&tile(Content::None), (1, 1));
+        known_map.set_tick(3);
+        // Seeing the same tile again is not a change
+        known_map.insert(&tile(Content::None), (0, 0));
+
+        let snapshot = KnownMapSnapshot::full(&known_map, &state);
+        assert_eq!(snapshot.tick, 3);
+        assert_eq!(coordinates(&snapshot), vec![(0, 0), (0, 1), (1, 1)]);
+
+        let diff = KnownMapSnapshot::diff(&known_map, 1, &state);
+        assert_eq!(diff.tick, 3);
+        assert_eq!(coordinates(&diff), vec![(0, 1), (1, 1)]);
+        assert!(diff.tiles.contains(&(tile(Content::Fire), (0, 1))));
+
+        assert!(KnownMapSnapshot::diff(&known_map, 3, &state).tiles.is_empty());
+    }
+
+    #[test]
+    fn diff_falls_back_to_the_whole_map_once_the_changes_are_no_longer_logged() {
+        let state = AiState::new();
+        let mut known_map = KnownMap::new();
+
+        known_map.set_tick(1);
+        known_map.insert(&tile(Content::None), (0, 0));
+        known_map.set_tick(2);
+        known_map.insert(&tile(Content::None), (0, 1));
+
+        for tick in 3..CHANGE_LOG_TICKS + 3 {
+            known_map.set_tick(tick);
+        }
+        known_map.insert(&tile(Content::None), (1, 1));
+
+        let recent = KnownMapSnapshot::diff(&known_map, CHANGE_LOG_TICKS, &state);
+        assert_eq!(coordinates(&recent), vec![(1, 1)]);
+
+        let old = KnownMapSnapshot::diff(&known_map, 0, &state);
+        assert_eq!(coordinates(&old), vec![(0, 0), (0, 1), (1, 1)]);
+    }
+}
@@ -1,24 +1,64 @@
-use robotics_lib::world::tile::Tile;
+use std::collections::{HashMap, VecDeque};
+use robotics_lib::world::tile::{Content, Tile};
 use robotics_lib::event::events::Event;
+use crate::error::AiError;
+use crate::goal::Goal;
+use crate::models::task::TaskSnapshot;
 
 /// State that should be consumed by a visualizer
 ///
 /// Properties:
+/// - tick: the number of process ticks performed by the robot
 /// - events_of_tick: the events occurred in a process tick
 /// - discovered_tiles: all discovered tiles during the process tick
+/// - changed_tiles: the tiles of the known map discovered or changed during the process tick, including the ones seen while moving
 /// - terminate: whether the robot has completed its goal
+/// - met_goals: the simple goals that made the robot terminate, empty until then
+/// - robot_coordinates: the coordinates of the robot at the end of the process tick, in the form of row, col
+/// - energy: the energy level of the robot at the end of the process tick
+/// - backpack: the contents of the backpack at the end of the process tick
+/// - current_task: the task the robot is performing
+/// - pending_tasks: the tasks in the priority queue, sorted by descending priority
+/// - ai_events_of_tick: the decisions taken by the ai in a process tick
+/// - task_stats: how many tasks were completed, retried, dropped and blacklisted since the start
+/// - resting: whether the robot is resting to regenerate energy instead of scanning and moving
+/// - last_errors: the most recent errors, oldest first, at most `MAX_LAST_ERRORS` of them
 pub struct AiState {
+    pub tick: usize,
     pub events_of_tick: Vec<Event>,
     pub discovered_tiles: Vec<(Tile, (usize, usize))>,
-    pub terminate: bool
+    pub changed_tiles: Vec<(Tile, (usize, usize))>,
+    pub terminate: bool,
+    pub met_goals: Vec<Goal>,
+    pub robot_coordinates: (usize, usize),
+    pub energy: usize,
+    pub backpack: HashMap<Content, usize>,
+    pub current_task: Option<TaskSnapshot>,
+    pub pending_tasks: Vec<TaskSnapshot>,
+    pub ai_events_of_tick: Vec<AiEvent>,
+    pub task_stats: TaskStats,
+    pub resting: bool,
+    pub last_errors: VecDeque<AiError>
 }
 
 impl AiState {
     pub(crate) fn new() -> AiState {
         AiState {
+            tick: 0,
             events_of_tick: vec![],
             discovered_tiles: vec![],
-            terminate: false
+            changed_tiles: vec![],
+            terminate: false,
+            met_goals: vec![],
+            robot_coordinates: (0, 0),
+            energy: 0,
+            backpack: HashMap::new(),
+            current_task: None,
+            pending_tasks: vec![],
+            ai_events_of_tick: vec![],
+            task_stats: TaskStats::default(),
+            resting: false,
+            last_errors: VecDeque::new()
         }
     }
 }
@@ -28,3 +68,30 @@ impl Default for AiState {
         AiState::new()
     }
 }
+
+/// Counters of the outcomes of the tasks
+///
+/// Properties:
+/// - completed: tasks completed successfully
+/// - retried: failures after which the task went back in the priority queue
+/// - dropped: tasks dropped after a permanent failure or after running out of attempts
+/// - blacklisted: coordinates blacklisted because unreachable
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskStats {
+    pub completed: usize,
+    pub retried: usize,
+    pub dropped: usize,
+    pub blacklisted: usize,
+}
+
+/// Decisions taken by the ai that visualizers might want to show
+#[derive(Clone, Debug, PartialEq)]
+pub enum AiEvent {
+    /// The current task was put back in the priority queue to perform a task discovered in the meantime
+    TaskPreempted {
+        preempted: TaskSnapshot,
+        by: TaskSnapshot,
+    },
+    /// The target tile of the task was observed again and doesn't hold the expected content anymore
+    StaleTaskDropped(TaskSnapshot),
+}
@@ -0,0 +1,72 @@
+use log::debug;
+use robotics_lib::interface::Direction;
+use crate::interface::WorldInterface;
+use crate::robot::TrashinatorRobot;
+
+/// Detection stage of a process tick: discovers new tiles and populates the priority queue of the robot
+pub trait DetectionStrategy {
+    fn detect(&mut self, robot: &mut TrashinatorRobot, world: &mut dyn WorldInterface);
+}
+
+/// A way of looking at the tiles near the robot
+#[derive(Clone, Debug, PartialEq)]
+pub enum Scan {
+    /// Looks at the 3x3 area centered on the robot
+    Around,
+    /// Looks at a 3 tiles wide strip in the direction, starting next to the robot and as long as the distance
+    OneDirection(Direction, usize),
+}
+
+/// Parameters used to choose the scan of a process tick
+///
+/// The energy consumed by each scan is estimated by the `EnergyPolicy` of the robot
+///
+/// Properties:
+/// - max_distance: the longest distance considered when looking in one direction
+/// - heading_weight: how many times more a tile is worth when it's in the direction of the current task or frontier
+/// - stale_after: process ticks after which a known tile is worth seeing again
+pub struct ScanConfig {
+    pub max_distance: usize,
+    pub heading_weight: usize,
+    pub stale_after: usize,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfig {
+            max_distance: 8,
+            heading_weight: 2,
+            stale_after: 30,
+        }
+    }
+}
+
+/// Chooses the scan that reveals the most unknown or stale tiles per energy, favouring the ones in the direction the robot is heading.
+/// Doesn't scan when no scan would reveal anything new
+pub struct DefaultDetectionStrategy {
+    pub scan: ScanConfig,
+}
+
+impl DefaultDetectionStrategy {
+    pub fn new(scan: ScanConfig) -> DefaultDetectionStrategy {
+        DefaultDetectionStrategy { scan }
+    }
+}
+
+impl Default for DefaultDetectionStrategy {
+    fn default() -> Self {
+        DefaultDetectionStrategy::new(ScanConfig::default())
+    }
+}
+
+impl DetectionStrategy for DefaultDetectionStrategy {
+    fn detect(&mut self, robot: &mut TrashinatorRobot, world: &mut dyn WorldInterface) {
+        match robot.plan_scan(&self.scan) {
+            Some(Scan::Around) => robot.discover_tiles_and_populate_pq(world),
+            Some(Scan::OneDirection(direction, distance)) => {
+                robot.discover_tiles_one_direction_and_populate_pq(world, direction, distance)
+            }
+            None => debug!("Nothing new can be seen, skipping the scan"),
+        }
+    }
+}
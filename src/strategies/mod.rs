@@ -0,0 +1,44 @@
+pub mod detection;
+pub mod movement;
+pub mod scheduling;
+pub mod scoring;
+
+use crate::strategies::detection::{DefaultDetectionStrategy, DetectionStrategy};
+use crate::strategies::movement::{DefaultMovementStrategy, MovementStrategy};
+use crate::strategies::scheduling::{DefaultSchedulingStrategy, SchedulingStrategy};
+
+/// The strategies used by the robot for each stage of a process tick
+///
+/// Properties:
+/// - detection: discovers tiles and fills the priority queue with tasks
+/// - scheduling: decides the current task
+/// - movement: moves the robot and performs the current task
+pub struct Strategies {
+    pub detection: Box<dyn DetectionStrategy>,
+    pub scheduling: Box<dyn SchedulingStrategy>,
+    pub movement: Box<dyn MovementStrategy>,
+}
+
+impl Strategies {
+    pub fn new(
+        detection: Box<dyn DetectionStrategy>,
+        scheduling: Box<dyn SchedulingStrategy>,
+        movement: Box<dyn MovementStrategy>,
+    ) -> Strategies {
+        Strategies {
+            detection,
+            scheduling,
+            movement,
+        }
+    }
+}
+
+impl Default for Strategies {
+    fn default() -> Self {
+        Strategies::new(
+            Box::new(DefaultDetectionStrategy::default()),
+            Box::new(DefaultSchedulingStrategy::default()),
+            Box::new(DefaultMovementStrategy),
+        )
+    }
+}
@@ -0,0 +1,16 @@
+use crate::interface::WorldInterface;
+use crate::robot::TrashinatorRobot;
+
+/// Movement stage of a process tick: moves the robot and performs the `current_task`
+pub trait MovementStrategy {
+    fn execute(&mut self, robot: &mut TrashinatorRobot, world: &mut dyn WorldInterface);
+}
+
+/// Walks towards the current task and performs it, explores the map when there is no task
+pub struct DefaultMovementStrategy;
+
+impl MovementStrategy for DefaultMovementStrategy {
+    fn execute(&mut self, robot: &mut TrashinatorRobot, world: &mut dyn WorldInterface) {
+        robot.execute_task(world);
+    }
+}
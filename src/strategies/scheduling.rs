@@ -0,0 +1,59 @@
+use crate::robot::TrashinatorRobot;
+
+/// Scheduling stage of a process tick: determines the `current_task` of the robot
+pub trait SchedulingStrategy {
+    fn schedule(&mut self, robot: &mut TrashinatorRobot);
+}
+
+/// Decides when a task discovered while performing another one should be performed first
+///
+/// Priorities are compared using `TaskAction::get_priority_for_task`, so the margin is not affected by the `TaskScorer`
+///
+/// Properties:
+/// - priority_margin: how much higher the priority of the new task must be
+/// - closer_ratio: how many times closer to the robot a new task with a priority that isn't lower must be
+pub struct PreemptionConfig {
+    pub priority_margin: usize,
+    pub closer_ratio: usize,
+}
+
+impl Default for PreemptionConfig {
+    fn default() -> Self {
+        PreemptionConfig {
+            priority_margin: 50,
+            closer_ratio: 3,
+        }
+    }
+}
+
+/// Drops the tasks whose target was seen without the expected content, adds a disposal task when the backpack holds too much garbage, scores the tasks in the queue again as the robot moves and orders the garbage tasks in a short route.
+/// Pops the task with the highest priority from the queue when there is no current task, following the garbage
+/// route, otherwise lets newly discovered tasks preempt the current one.
+/// Finally makes sure the garbage of the current task fits in the backpack, going to a bin first if it doesn't
+pub struct DefaultSchedulingStrategy {
+    pub preemption: PreemptionConfig,
+}
+
+impl DefaultSchedulingStrategy {
+    pub fn new(preemption: PreemptionConfig) -> DefaultSchedulingStrategy {
+        DefaultSchedulingStrategy { preemption }
+    }
+}
+
+impl Default for DefaultSchedulingStrategy {
+    fn default() -> Self {
+        DefaultSchedulingStrategy::new(PreemptionConfig::default())
+    }
+}
+
+impl SchedulingStrategy for DefaultSchedulingStrategy {
+    fn schedule(&mut self, robot: &mut TrashinatorRobot) {
+        robot.validate_tasks();
+        robot.plan_disposal();
+        robot.rescore_pq();
+        robot.plan_garbage_route();
+        robot.preempt_current_task(&self.preemption);
+        robot.determine_current_task();
+        robot.ensure_backpack_space();
+    }
+}
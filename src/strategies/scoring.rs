@@ -0,0 +1,100 @@
+use crate::models::task::TaskAction;
+
+/// Everything known about a task when computing its priority
+///
+/// Properties:
+/// - action: the action of the task
+/// - coordinates: the coordinates of the task, in the form of row, col
+/// - distance: steps needed to reach a tile adjacent to the task
+/// - energy_cost: predicted energy needed to reach a tile adjacent to the task
+pub struct TaskScoreInput<'a> {
+    pub action: &'a TaskAction,
+    pub coordinates: (usize, usize),
+    pub distance: usize,
+    pub energy_cost: usize,
+}
+
+/// Computes the priority of the tasks stored in the priority queue, higher priorities are performed first
+///
+/// Tasks are scored again as the robot moves
+pub trait TaskScorer {
+    fn score(&self, input: &TaskScoreInput) -> usize;
+}
+
+/// Scores tasks by their action, lowered by the distance and energy needed to reach them
+///
+/// Properties:
+/// - action_weight: multiplies the priority of the action (`TaskAction::get_priority_for_task`)
+/// - distance_weight: subtracted for each step needed to reach the task
+/// - energy_weight: subtracted for each unit of energy needed to reach the task
+pub struct DefaultTaskScorer {
+    pub action_weight: usize,
+    pub distance_weight: usize,
+    pub energy_weight: usize,
+}
+
+impl Default for DefaultTaskScorer {
+    fn default() -> Self {
+        DefaultTaskScorer {
+            action_weight: 100,
+            distance_weight: 2,
+            energy_weight: 1,
+        }
+    }
+}
+
+impl TaskScorer for DefaultTaskScorer {
+    fn score(&self, input: &TaskScoreInput) -> usize {
+        (input.action.get_priority_for_task() * self.action_weight)
+            .saturating_sub(input.distance * self.distance_weight)
+            .saturating_sub(input.energy_cost * self.energy_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(scorer: &DefaultTaskScorer, action: TaskAction, distance: usize, energy_cost: usize) -> usize {
+        scorer.score(&TaskScoreInput {
+            action: &action,
+            coordinates: (0, 0),
+            distance,
+            energy_cost,
+        })
+    }
+
+    #[test]
+    fn default_scorer() {
+        let scorer = DefaultTaskScorer::default();
+
+        assert_eq!(score(&scorer, TaskAction::DestroyFire, 0, 0), 10_000);
+        assert_eq!(score(&scorer, TaskAction::DestroyFire, 100, 300), 9_500);
+        assert_eq!(score(&scorer, TaskAction::DestroyGarbage, 10, 20), 4_960);
+        // Never below zero
+        assert_eq!(score(&scorer, TaskAction::PutGarbageInBin, 100, 0), 0);
+    }
+
+    #[test]
+    fn actions_weigh_more_than_distance() {
+        let scorer = DefaultTaskScorer::default();
+
+        // A far fire comes before a garbage next to the robot
+        assert!(score(&scorer, TaskAction::DestroyFire, 100, 300) > score(&scorer, TaskAction::DestroyGarbage, 1, 1));
+        // Among tasks with the same action the cheaper one comes first
+        assert!(score(&scorer, TaskAction::DestroyGarbage, 5, 5) > score(&scorer, TaskAction::DestroyGarbage, 5, 20));
+        assert!(score(&scorer, TaskAction::DestroyGarbage, 4, 10) > score(&scorer, TaskAction::DestroyGarbage, 6, 10));
+    }
+
+    #[test]
+    fn custom_weights() {
+        let scorer = DefaultTaskScorer {
+            action_weight: 1,
+            distance_weight: 0,
+            energy_weight: 2,
+        };
+
+        assert_eq!(score(&scorer, TaskAction::DestroyFire, 50, 10), 80);
+        assert_eq!(score(&scorer, TaskAction::DestroyGarbage, 50, 30), 0);
+    }
+}